@@ -0,0 +1,155 @@
+//! Throughput benchmarks of the reduction functions.
+//!
+//! Run using `cargo bench --features bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use m61_modulus::*;
+
+/// The input sizes in bytes.
+const SIZES: [(usize, &str); 3] = [(1 << 10, "1KB"), (1 << 20, "1MB"), (1 << 26, "64MB")];
+
+/// The input size in bytes used for comparing the backends.
+const BACKEND_SIZE: usize = 1 << 20;
+
+/// Creates an input of `bytes` bytes with pseudo-random digits.
+fn input<T: TryFrom<u64>>(bytes: usize) -> Vec<T> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    (0..bytes / core::mem::size_of::<T>())
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let bits = 8 * core::mem::size_of::<T>() as u32;
+            T::try_from(state >> (64 - bits)).ok().unwrap()
+        })
+        .collect()
+}
+
+/// Benchmarks `reduce_m61` and `reduce_m61_parallelized` for all sizes.
+macro_rules! bench_reduce {
+    ($c:expr, $type:ty) => {{
+        let threads = std::thread::available_parallelism().map_or(1, |x| x.get());
+        let mut group = $c.benchmark_group(concat!("reduce_", stringify!($type)));
+
+        for (bytes, label) in SIZES {
+            let v: Vec<$type> = input(bytes);
+            group.throughput(Throughput::Bytes(bytes as u64));
+
+            group.bench_with_input(BenchmarkId::new("reduce_m61", label), &v, |b, v| {
+                b.iter(|| black_box(&v[..]).reduce_m61())
+            });
+            group.bench_with_input(
+                BenchmarkId::new("reduce_m61_parallelized", label),
+                &v,
+                |b, v| b.iter(|| black_box(&v[..]).reduce_m61_parallelized(threads)),
+            );
+        }
+
+        group.finish();
+    }};
+}
+
+/// Benchmarks the serial and parallelized reductions for inputs around
+/// the size from which on the latter spawns threads, which is
+/// `2^14` elements per available thread.
+fn crossover(c: &mut Criterion) {
+    let threads = std::thread::available_parallelism().map_or(1, |x| x.get());
+    let mut group = c.benchmark_group("crossover_u64");
+
+    for factor in [1, 2, 4] {
+        let len = factor * threads * (1 << 14) / 2;
+        let v: Vec<u64> = input(8 * len);
+        group.throughput(Throughput::Bytes(8 * len as u64));
+
+        group.bench_with_input(BenchmarkId::new("reduce_m61", len), &v, |b, v| {
+            b.iter(|| black_box(&v[..]).reduce_m61())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("reduce_m61_parallelized", len),
+            &v,
+            |b, v| b.iter(|| black_box(&v[..]).reduce_m61_parallelized(threads)),
+        );
+    }
+
+    group.finish();
+}
+
+/// Benchmarks the parallelized reduction for a range of thread counts,
+/// which aren't limited to the available parallelism. This shows the
+/// overhead of spawning and joining the threads, and combining their results.
+fn threads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("threads_u64");
+    let v: Vec<u64> = input(SIZES[2].0);
+    group.throughput(Throughput::Bytes(SIZES[2].0 as u64));
+
+    for threads in [1, 2, 4, 8, 16, 32] {
+        group.bench_with_input(BenchmarkId::from_parameter(threads), &v, |b, v| {
+            b.iter(|| {
+                m61_modulus::backend::reduce_u64_threads(
+                    black_box(v),
+                    threads,
+                    ParallelStrategy::Static,
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmarks every backend available on the current CPU.
+macro_rules! bench_backends {
+    ($c:expr, $type:ty, $func:ident) => {{
+        let mut group = $c.benchmark_group(concat!("backends_", stringify!($type)));
+        let v: Vec<$type> = input(BACKEND_SIZE);
+        group.throughput(Throughput::Bytes(BACKEND_SIZE as u64));
+
+        for backend in m61_modulus::backend::available() {
+            group.bench_with_input(BenchmarkId::from_parameter(backend.name()), &v, |b, v| {
+                b.iter(|| backend.$func(black_box(v)))
+            });
+        }
+
+        group.finish();
+    }};
+}
+
+/// Benchmarks every backend available on the current CPU with inputs
+/// aligned to 64 bytes, and with inputs offset by one element from that.
+fn alignment(c: &mut Criterion) {
+    let mut group = c.benchmark_group("alignment_u64");
+    let len = BACKEND_SIZE / 8;
+    let v: Vec<u64> = input(BACKEND_SIZE + 64);
+    let start = v.as_ptr().align_offset(64);
+    group.throughput(Throughput::Bytes(BACKEND_SIZE as u64));
+
+    for backend in m61_modulus::backend::available() {
+        for (offset, label) in [(0, "aligned"), (1, "unaligned")] {
+            let s = &v[start + offset..][..len];
+            group.bench_with_input(BenchmarkId::new(backend.name(), label), s, |b, s| {
+                b.iter(|| backend.reduce_u64(black_box(s)))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn reduce(c: &mut Criterion) {
+    force_init();
+
+    bench_reduce!(c, u8);
+    bench_reduce!(c, u16);
+    bench_reduce!(c, u32);
+    bench_reduce!(c, u64);
+}
+
+fn backends(c: &mut Criterion) {
+    bench_backends!(c, u8, reduce_u8);
+    bench_backends!(c, u16, reduce_u16);
+    bench_backends!(c, u32, reduce_u32);
+    bench_backends!(c, u64, reduce_u64);
+}
+
+criterion_group!(benches, reduce, crossover, threads, backends, alignment);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+//! Detects whether the AVX512 intrinsics can be used.
+//!
+//! They are stable since Rust 1.89. Older compilers only provide them
+//! on nightly, which is opted into using the `nightly` feature.
+//! If either holds, the `avx512_intrinsics` cfg is set.
+
+use std::env;
+use std::process::Command;
+
+/// The first minor version of Rust 1.x with stable AVX512 intrinsics.
+const STABLE_AVX512: u32 = 89;
+
+/// Returns the minor version of the compiler, or `None` if
+/// it can't be determined.
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+
+    // The output has the form `rustc 1.89.0 (...)`.
+    let mut parts = version.split_whitespace().nth(1)?.split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(avx512_intrinsics, avx512_nightly)");
+
+    let stable = rustc_minor_version().is_some_and(|minor| minor >= STABLE_AVX512);
+    let nightly = env::var_os("CARGO_FEATURE_NIGHTLY").is_some();
+
+    if stable || nightly {
+        println!("cargo:rustc-cfg=avx512_intrinsics");
+    }
+    if nightly && !stable {
+        // The intrinsics still have to be enabled using a feature gate.
+        println!("cargo:rustc-cfg=avx512_nightly");
+    }
+}
@@ -0,0 +1,330 @@
+//! Incremental calculation of reductions.
+//!
+//! A number `x` with digits `a_i` can be split into two parts
+//! `x = l + 2^k h`, where `l` consists of the lowest `k` bits
+//! of `x`. Since `2^u = 2^v (mod 2^61 - 1)` iff `u = v (mod 61)`,
+//! the reduction of `x` can be calculated from the reductions of
+//! `l` and `h` alone, only requiring knowledge of `k mod 61`.
+//! This allows the digits to be supplied in little-endian
+//! chunks, without ever holding all of them in memory.
+
+use crate::definition::{shift_exponent, M61, POW2_MOD};
+use crate::M61Reduction;
+
+/// Calculates the reduction of a number whose digits
+/// are supplied incrementally in little-endian ordering.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let mut acc = M61Accumulator::new();
+/// acc.push_u16(&[1, 2]);
+/// acc.push_u8(&[3, 4, 5]);
+///
+/// assert_eq!(acc.get(), [1u8, 0, 2, 0, 3, 4, 5].reduce_m61());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct M61Accumulator {
+    /// The reduction of all digits supplied so far.
+    value: M61,
+    /// The amount of bits supplied so far, modulo 61.
+    shift: u32,
+}
+
+/// Helper macro for the creation of the `push_*` functions.
+macro_rules! make_push {
+    ($name:ident, $type:ty) => {
+        #[doc = concat!("Appends `digits` as more significant `", stringify!($type), "` digits.")]
+        #[inline]
+        pub fn $name(&mut self, digits: &[$type]) {
+            self.append(digits.reduce_m61(), digits.len(), <$type>::BITS);
+        }
+    };
+}
+
+impl M61Accumulator {
+    /// Creates an accumulator representing the number zero.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            value: M61(0),
+            shift: 0,
+        }
+    }
+
+    /// Returns the reduction of all digits supplied so far.
+    #[inline(always)]
+    #[must_use]
+    pub const fn get(&self) -> M61 {
+        self.value
+    }
+
+    make_push!(push_u8, u8);
+    make_push!(push_u16, u16);
+    make_push!(push_u32, u32);
+    make_push!(push_u64, u64);
+
+    /// Removes the least significant `u64` digit `old_value`, shifting the
+    /// remaining digits down by 64 bits. Together with [`M61Accumulator::push_u64`],
+    /// this allows maintaining the reduction of a sliding window over a
+    /// sequence of limbs, as required by Rabin-Karp-style rolling checks.
+    ///
+    /// The accumulator does not store the digits themselves, so the caller
+    /// has to uphold the following contract:
+    /// * `old_value` is the least significant digit supplied so far,
+    ///   i.e. the oldest limb of the window which hasn't been removed yet.
+    /// * `total_len_bits` is the length of the window in bits before the
+    ///   removal, i.e. the number of bits supplied so far minus 64 times the
+    ///   number of removed limbs. It is only used for checking the contract
+    ///   in debug builds.
+    ///
+    /// If the contract is violated, the result is unspecified.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let limbs = [1u64, 2, 3, 4];
+    /// let mut acc = M61Accumulator::new();
+    /// acc.push_u64(&limbs[..3]);
+    ///
+    /// // Advance the window by one limb.
+    /// acc.push_u64(&limbs[3..]);
+    /// acc.pop_u64(limbs[0], 4 * 64);
+    ///
+    /// assert_eq!(acc.get(), limbs[1..].reduce_m61());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if debug assertions are enabled and `total_len_bits`
+    /// is inconsistent with the digits supplied so far.
+    #[inline]
+    pub fn pop_u64(&mut self, old_value: u64, total_len_bits: usize) {
+        debug_assert!(
+            total_len_bits >= 64 && total_len_bits % 61 == self.shift as usize,
+            "window length is inconsistent with the supplied digits"
+        );
+
+        // Dividing by `2^64` is the same as multiplying by `2^58`,
+        // since `2^64 * 2^58 = 2^122 = 1 (mod 2^61 - 1)`.
+        self.value = (self.value - M61::from(old_value)) * POW2_MOD[58];
+        self.shift = (self.shift + 58) % 61;
+    }
+
+    /// Adds the reduction `value` of a number consisting of
+    /// `len` digits with `bits` bits each as the most significant part.
+    #[inline]
+    fn append(&mut self, value: M61, len: usize, bits: u32) {
+        self.value += value * POW2_MOD[self.shift as usize];
+        let bits = shift_exponent(len, bits as usize) as u32;
+        self.shift = (self.shift + bits) % 61;
+    }
+}
+
+/// Appends the limbs as the next more significant `u64` digits,
+/// least significant limb first.
+impl Extend<u64> for M61Accumulator {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        for limb in iter {
+            self.value += M61::from(limb) * POW2_MOD[self.shift as usize];
+            // `64 = 3 (mod 61)`.
+            self.shift = (self.shift + 3) % 61;
+        }
+    }
+}
+
+/// Calculates the reduction of the number whose `u64` digits
+/// are yielded by the iterator in little-endian ordering,
+/// i.e. least significant limb first.
+///
+/// The result is the same as collecting the limbs into a
+/// vector and calling [`M61Reduction::reduce_m61`] on it,
+/// without allocating the vector.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let limbs = [1u64, 2, 3];
+/// let x: M61 = limbs.iter().copied().collect();
+///
+/// assert_eq!(x, limbs.reduce_m61());
+/// // The second limb has the weight `2^64 = 8 (mod 2^61 - 1)`.
+/// assert_eq!([0u64, 1].into_iter().collect::<M61>(), M61::from(8u64));
+/// ```
+impl FromIterator<u64> for M61 {
+    fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        let mut acc = M61Accumulator::new();
+        acc.extend(iter);
+        acc.get()
+    }
+}
+
+/// Calculates `x mod (2^61 - 1)`, where `x` is the number
+/// whose base `2^8` digits are read from `reader` in little-endian ordering.
+///
+/// The input is processed in chunks using a fixed-size buffer, which
+/// means that arbitrarily large inputs, like files, sockets or pipes,
+/// can be handled without reading them into memory first.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let bytes: Vec<u8> = (0..100_000u32).map(|x| x as u8).collect();
+/// let x = reduce_m61_reader(std::io::Cursor::new(&bytes)).unwrap();
+///
+/// assert_eq!(x, bytes.reduce_m61());
+/// ```
+///
+/// # Errors
+///
+/// Any error returned by `reader` other than [`std::io::ErrorKind::Interrupted`]
+/// is returned as is.
+#[cfg(feature = "std")]
+pub fn reduce_m61_reader<R: std::io::Read>(mut reader: R) -> std::io::Result<M61> {
+    use std::io::ErrorKind;
+
+    let mut buffer = [0u8; 1 << 13];
+    let mut acc = M61Accumulator::new();
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(acc.get()),
+            Ok(len) => acc.push_u8(&buffer[..len]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    quickcheck::quickcheck! {
+        fn push_u8_correct(slices: Vec<Vec<u8>>) -> bool {
+            let mut acc = M61Accumulator::new();
+            for s in &slices {
+                acc.push_u8(s);
+            }
+
+            acc.get() == slices.concat().reduce_m61()
+        }
+
+        fn push_u64_correct(slices: Vec<Vec<u64>>) -> bool {
+            let mut acc = M61Accumulator::new();
+            for s in &slices {
+                acc.push_u64(s);
+            }
+
+            acc.get() == slices.concat().reduce_m61()
+        }
+
+        fn collect_correct(limbs: Vec<u64>) -> bool {
+            limbs.iter().copied().collect::<M61>() == limbs.reduce_m61()
+        }
+
+        fn extend_after_push_correct(a: Vec<u8>, b: Vec<u64>) -> bool {
+            let mut acc = M61Accumulator::new();
+            acc.push_u8(&a);
+            acc.extend(b.iter().copied());
+            acc.push_u8(&a);
+
+            let mut bytes = a.clone();
+            bytes.extend(b.iter().flat_map(|x| x.to_le_bytes()));
+            bytes.extend(&a);
+
+            acc.get() == bytes.reduce_m61()
+        }
+
+        fn push_mixed_correct(a: Vec<u16>, b: Vec<u32>) -> bool {
+            let mut acc = M61Accumulator::new();
+            acc.push_u16(&a);
+            acc.push_u32(&b);
+
+            let mut bytes = Vec::new();
+            bytes.extend(a.iter().flat_map(|x| x.to_le_bytes()));
+            bytes.extend(b.iter().flat_map(|x| x.to_le_bytes()));
+
+            acc.get() == bytes.reduce_m61()
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn rolling_window_correct(limbs: Vec<u64>, window: usize) -> bool {
+            let window = window % 8 + 1;
+            if limbs.len() < window {
+                return true;
+            }
+
+            let mut acc = M61Accumulator::new();
+            acc.push_u64(&limbs[..window]);
+
+            (window..limbs.len()).all(|i| {
+                acc.push_u64(&limbs[i..i + 1]);
+                acc.pop_u64(limbs[i - window], 64 * (window + 1));
+                acc.get() == limbs[i + 1 - window..=i].reduce_m61()
+            })
+        }
+
+        fn push_then_pop_restores(limb: u64) -> bool {
+            let mut acc = M61Accumulator::new();
+            acc.push_u64(&[limb]);
+            acc.pop_u64(limb, 64);
+            acc == M61Accumulator::new()
+        }
+    }
+
+    #[test]
+    fn pop_all_limbs() {
+        let limbs = [u64::MAX, 0, 1 << 61, 12345];
+        let mut acc = M61Accumulator::new();
+        acc.push_u64(&limbs);
+
+        for (i, &limb) in limbs.iter().enumerate() {
+            acc.pop_u64(limb, 64 * (limbs.len() - i));
+            assert_eq!(acc.get(), limbs[i + 1..].reduce_m61());
+        }
+        assert_eq!(acc, M61Accumulator::new());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "window length is inconsistent")]
+    fn pop_inconsistent_length() {
+        let mut acc = M61Accumulator::new();
+        acc.push_u64(&[1, 2]);
+        acc.pop_u64(1, 64);
+    }
+
+    /// Reader returning at most 7 bytes at a time,
+    /// interrupting every other call.
+    struct SlowReader<'a>(&'a [u8], bool);
+
+    impl std::io::Read for SlowReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+
+            let len = buf.len().min(self.0.len()).min(7);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn reduce_reader_correct() {
+        for len in [0, 1, 100, 1 << 13, (1 << 15) + 3] {
+            let v: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+
+            let actual = reduce_m61_reader(std::io::Cursor::new(&v)).unwrap();
+            assert_eq!(actual, v.reduce_m61());
+
+            let actual = reduce_m61_reader(SlowReader(&v, false)).unwrap();
+            assert_eq!(actual, v.reduce_m61());
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Access to the individual implementations of the reduction functions,
+//! used for comparing them in benchmarks. Not part of the public API.
+
+use crate::definition::M61;
+use crate::ParallelStrategy;
+
+/// A set of implementations of the reduction functions.
+#[derive(Clone, Copy)]
+pub struct Backend {
+    name: &'static str,
+    u8: unsafe fn(&[u8]) -> M61,
+    u16: unsafe fn(&[u16]) -> M61,
+    u32: unsafe fn(&[u32]) -> M61,
+    u64: unsafe fn(&[u64]) -> M61,
+}
+
+/// Creates a [`Backend`] from a module containing the four reduction functions.
+macro_rules! backend {
+    ($name:expr, $module:path) => {{
+        use $module as module;
+
+        crate::backend::Backend::new(
+            $name,
+            module::reduce_u8,
+            module::reduce_u16,
+            module::reduce_u32,
+            module::reduce_u64,
+        )
+    }};
+}
+
+#[allow(unused_imports)]
+pub(crate) use backend;
+
+impl Backend {
+    /// Creates a backend from its functions.
+    ///
+    /// # Safety
+    ///
+    /// The functions must be safe to call on the current CPU.
+    pub(crate) const unsafe fn new(
+        name: &'static str,
+        u8: unsafe fn(&[u8]) -> M61,
+        u16: unsafe fn(&[u16]) -> M61,
+        u32: unsafe fn(&[u32]) -> M61,
+        u64: unsafe fn(&[u64]) -> M61,
+    ) -> Self {
+        Self {
+            name,
+            u8,
+            u16,
+            u32,
+            u64,
+        }
+    }
+
+    /// The name of the backend.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Reduces `s` using this backend.
+    pub fn reduce_u8(&self, s: &[u8]) -> M61 {
+        // SAFETY: Guaranteed by the constructor.
+        unsafe { (self.u8)(s) }
+    }
+
+    /// Reduces `s` using this backend.
+    pub fn reduce_u16(&self, s: &[u16]) -> M61 {
+        // SAFETY: Guaranteed by the constructor.
+        unsafe { (self.u16)(s) }
+    }
+
+    /// Reduces `s` using this backend.
+    pub fn reduce_u32(&self, s: &[u32]) -> M61 {
+        // SAFETY: Guaranteed by the constructor.
+        unsafe { (self.u32)(s) }
+    }
+
+    /// Reduces `s` using this backend.
+    pub fn reduce_u64(&self, s: &[u64]) -> M61 {
+        // SAFETY: Guaranteed by the constructor.
+        unsafe { (self.u64)(s) }
+    }
+}
+
+impl core::fmt::Debug for Backend {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Backend").field(&self.name).finish()
+    }
+}
+
+/// Returns all backends supported by the current CPU.
+///
+/// The list starts with the scalar implementations, followed by the one
+/// used by [`M61Reduction::reduce_m61`](crate::M61Reduction::reduce_m61),
+/// and the individual vectorized implementations.
+pub fn available() -> Vec<Backend> {
+    // SAFETY: The fallback is always safe to call, while the dispatch
+    // only defers to implementations whose safety conditions are met.
+    let mut backends = unsafe {
+        vec![
+            backend!("scalar", crate::fallback),
+            backend!("scalar-forward", crate::fallback::forward),
+            backend!("scalar-swar", crate::fallback_swar),
+            backend!("dispatch", crate::implementation),
+        ]
+    };
+
+    crate::implementation::push_backends(&mut backends);
+    backends
+}
+
+/// Reduces `s` in parallel using up to `thread_count` threads, without limiting
+/// them to the available parallelism like
+/// [`reduce_m61_parallelized_with`](crate::M61Reduction::reduce_m61_parallelized_with).
+/// This allows testing multi-threaded schedules on any host.
+pub fn reduce_u8_threads(s: &[u8], thread_count: usize, strategy: ParallelStrategy) -> M61 {
+    crate::parallelized::reduce_u8_exact(s, thread_count, strategy)
+}
+
+/// Reduces `s` in parallel using up to `thread_count` threads, without limiting
+/// them to the available parallelism like
+/// [`reduce_m61_parallelized_with`](crate::M61Reduction::reduce_m61_parallelized_with).
+/// This allows testing multi-threaded schedules on any host.
+pub fn reduce_u64_threads(s: &[u64], thread_count: usize, strategy: ParallelStrategy) -> M61 {
+    crate::parallelized::reduce_u64_exact(s, thread_count, strategy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::M61Reduction;
+
+    quickcheck::quickcheck! {
+        fn backends_agree(v: Vec<u64>) -> bool {
+            let bytes: Vec<u8> = v.iter().flat_map(|x| x.to_le_bytes()).collect();
+            let expected = v.reduce_m61_scalar();
+
+            available().iter().all(|backend| {
+                backend.reduce_u64(&v) == expected && backend.reduce_u8(&bytes) == expected
+            })
+        }
+    }
+}
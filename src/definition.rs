@@ -9,35 +9,694 @@ use core::ops;
 /// digit sums base `2^61`.
 pub(crate) const MODULUS: u64 = (1 << 61) - 1;
 
+/// The powers of two modulo `2^61 - 1`, i.e. `POW2_MOD[k] = 2^k` for `k < 61`.
+///
+/// Since `2^61` is congruent to one, `2^k` is congruent to `2^(k mod 61)`
+/// for every `k`, which means that the table covers all powers of two.
+/// Multiplying by `2^k` shifts a number by `k` bits, which is the core
+/// identity used for combining the reductions of adjacent parts of a number.
+/// For `k < 61`, the entries are just `1 << k`, which already are the
+/// canonical representatives.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// // Shifting by 100 bits.
+/// let x = M61::from(3u32) * POW2_MOD[100 % 61];
+///
+/// assert_eq!(x, M61::from(3u128 << 100));
+/// ```
+pub const POW2_MOD: [M61; 61] = {
+    let mut table = [M61(0); 61];
+    let mut k = 0;
+    while k < 61 {
+        table[k] = M61(1 << k);
+        k += 1;
+    }
+    table
+};
+
+/// Returns `len * bits mod 61`, the exponent of the power of two
+/// by which a part consisting of `len` digits with `bits` bits each
+/// shifts the parts above it.
+///
+/// Both factors are reduced first, since their product may overflow for
+/// large inputs, especially on 32-bit targets. Reducing the exponent is
+/// valid because `2^61` is congruent to one.
+#[inline(always)]
+pub(crate) const fn shift_exponent(len: usize, bits: usize) -> usize {
+    (len % 61) * (bits % 61) % 61
+}
+
 /// When calculating the reduction of an arbitary precision integer
 /// using a digit sum, the sum itself must be reduced aswell.
 /// This function performs this reduction, assuming that
-/// are themselves partially reduced, meaning `x <= 2 * (2^61 - 1)`.
+/// are themselves partially reduced, meaning `x < 3 * (2^61 - 1)`.
+///
+/// The digit sum of the last reduction step in the vectorized
+/// implementations is bounded by `2 * (2^61 - 1) + 63`. Debug builds
+/// check the precondition to catch violations of this invariant early.
 #[inline(always)]
 pub(crate) fn final_reduction(mut x: u64) -> M61 {
+    debug_assert!(x < 3 * MODULUS, "digit sum is not partially reduced");
+
     if x >= MODULUS {
         x -= MODULUS;
     }
 
     if x >= MODULUS {
-        M61(x - MODULUS)
-    } else {
-        M61(x)
+        x -= MODULUS;
     }
+
+    debug_assert!(x < MODULUS);
+    M61(x)
 }
 
 /// A 64-bit integer in which arithmetic is performed modulp `2^61 - 1`.
+///
+/// ## Formatting
+///
+/// All formatting traits behave like the ones of `u64`. When using
+/// the alternate flag with [`fmt::Display`] or [`fmt::Debug`], the
+/// modulus is appended to make the output self-describing.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// assert_eq!(format!("{}", M61::from(42u32)), "42");
+/// assert_eq!(format!("{:#}", M61::from(42u32)), "42 (mod 2^61-1)");
+/// ```
+///
+/// For output of a fixed width, e.g. when comparing values in logs,
+/// the recommended format is `{:016x}`, which zero-pads the value to
+/// 16 hexadecimal digits. Every value fits, since it has at most 61 bits.
+/// [`M61::to_hex61`] produces the same without requiring an allocation.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// assert_eq!(format!("{:016x}", M61::from(42u32)), "000000000000002a");
+/// ```
+///
+/// ## Default
+///
+/// The [`Default`] value is zero, the additive identity, like for the
+/// primitive integers. It is not suitable as the starting value of a
+/// product, for which [`M61::one`] or [`M61::ONE`] should be used instead.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// assert_eq!(M61::default(), M61::zero());
+/// assert_ne!(M61::default(), M61::one());
+/// ```
+///
+/// ## Ordering
+///
+/// Every value is stored as its canonical representative, the unique
+/// integer in the range `0..2^61 - 1` it is congruent to. The implementations
+/// of [`PartialOrd`] and [`Ord`] compare these representatives as plain integers.
+/// This ordering has no meaning in the field itself, since it isn't compatible
+/// with the arithmetic: `x < y` doesn't imply `x + z < y + z`.
+/// [`M61::min_repr`] and [`M61::max_repr`] make this explicit at the call site.
+///
+/// ## Remainder
+///
+/// In the field, the remainder of a division by a nonzero value is always
+/// zero, which makes it useless as an operation. Instead, [`ops::Rem`]
+/// calculates the remainder of the canonical representatives as plain integers,
+/// like the ordering does. Just like division, it panics if the divisor is zero.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// assert_eq!(M61::from(17u32) % M61::from(5u32), M61::from(2u32));
+/// ```
 #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct M61(pub(crate) u64);
 
 impl M61 {
+    /// The additive identity.
+    pub const ZERO: Self = Self(0);
+
+    /// The multiplicative identity.
+    pub const ONE: Self = Self(1);
+
+    /// Returns the additive identity, which is also the [`Default`] value.
+    #[inline(always)]
+    #[must_use]
+    pub const fn zero() -> Self {
+        Self::ZERO
+    }
+
+    /// Returns the multiplicative identity.
+    #[inline(always)]
+    #[must_use]
+    pub const fn one() -> Self {
+        Self::ONE
+    }
+
+    /// Reduces `value`, like the [`From<u64>`] implementation,
+    /// but usable in constant contexts.
+    #[inline]
+    #[must_use]
+    pub const fn from_u64_const(value: u64) -> Self {
+        let tmp = (value & MODULUS) + (value >> 61);
+        if tmp >= MODULUS {
+            Self(tmp - MODULUS)
+        } else {
+            Self(tmp)
+        }
+    }
+
+    /// Calculates `self + rhs`, like the [`ops::Add`] implementation,
+    /// but usable in constant contexts.
+    #[inline]
+    #[must_use]
+    pub const fn add_const(self, rhs: Self) -> Self {
+        let x = self.0 + rhs.0;
+        if x >= MODULUS {
+            Self(x - MODULUS)
+        } else {
+            Self(x)
+        }
+    }
+
+    /// Calculates `self - rhs`, like the [`ops::Sub`] implementation,
+    /// but usable in constant contexts.
+    #[inline]
+    #[must_use]
+    pub const fn sub_const(self, rhs: Self) -> Self {
+        let x = self.0 + MODULUS - rhs.0;
+        if x >= MODULUS {
+            Self(x - MODULUS)
+        } else {
+            Self(x)
+        }
+    }
+
+    /// Calculates `self * rhs`, like the [`ops::Mul`] implementation,
+    /// but usable in constant contexts.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// const X: M61 = M61::from_u64_const(1 << 40).mul_const(M61::from_u64_const(1 << 30));
+    /// assert_eq!(X, M61::from(1u64 << 9));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn mul_const(self, rhs: Self) -> Self {
+        let x = self.0 as u128 * rhs.0 as u128;
+        let mut hi = (x >> 61) as u64;
+        let mut lo = (x as u64) & MODULUS;
+        lo = lo.wrapping_add(hi);
+        hi = lo.wrapping_sub(MODULUS);
+        if lo < MODULUS {
+            Self(lo)
+        } else {
+            Self(hi)
+        }
+    }
+
     /// Returns the contained value.
     #[inline(always)]
     #[must_use]
     pub const fn get(self) -> u64 {
         self.0
     }
+
+    /// Creates a value from its canonical representative without reducing it.
+    ///
+    /// `value` must be smaller than `2^61 - 1`, which is only checked if
+    /// debug assertions are enabled. Violating this does not lead to
+    /// undefined behavior, but arithmetic on the resulting value produces
+    /// unspecified results. Use [`From`] for arbitrary integers.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_raw(value: u64) -> Self {
+        debug_assert!(value < MODULUS, "value is not canonical");
+        Self(value)
+    }
+
+    /// Returns the contained value, the inverse of [`M61::from_raw`].
+    #[inline(always)]
+    #[must_use]
+    pub const fn into_raw(self) -> u64 {
+        self.0
+    }
+
+    /// Returns whether the contained value is the canonical
+    /// representative, i.e. smaller than `2^61 - 1`.
+    ///
+    /// This holds for every value created using the functions of this crate,
+    /// and can only be violated using [`M61::from_raw`] or [`M61::from_u64_slice`]
+    /// in builds without debug assertions.
+    #[inline(always)]
+    #[must_use]
+    pub const fn is_canonical(self) -> bool {
+        self.0 < MODULUS
+    }
+
+    /// Compares the canonical representatives of `self` and `other`.
+    ///
+    /// This is the same as [`Ord::cmp`], but additionally
+    /// checks that both values are canonical in debug builds.
+    #[inline]
+    #[must_use]
+    pub fn cmp_canonical(self, other: Self) -> core::cmp::Ordering {
+        debug_assert!(self.0 < MODULUS && other.0 < MODULUS);
+        self.0.cmp(&other.0)
+    }
+
+    /// Returns the value with the smaller canonical representative.
+    #[inline]
+    #[must_use]
+    pub fn min_repr(self, other: Self) -> Self {
+        match self.cmp_canonical(other) {
+            core::cmp::Ordering::Greater => other,
+            _ => self,
+        }
+    }
+
+    /// Returns the value with the larger canonical representative.
+    #[inline]
+    #[must_use]
+    pub fn max_repr(self, other: Self) -> Self {
+        match self.cmp_canonical(other) {
+            core::cmp::Ordering::Greater => self,
+            _ => other,
+        }
+    }
+
+    /// Returns the memory representation of the contained
+    /// value as a byte array in little-endian byte order.
+    #[inline(always)]
+    #[must_use]
+    pub const fn to_le_bytes(self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+
+    /// Creates a value from its memory representation as a byte
+    /// array in little-endian byte order.
+    ///
+    /// Unlike the reduction functions, this does not accept
+    /// arbitrary input. `None` is returned if the decoded integer
+    /// is not smaller than `2^61 - 1`.
+    #[inline]
+    #[must_use]
+    pub const fn from_le_bytes(bytes: [u8; 8]) -> Option<Self> {
+        let value = u64::from_le_bytes(bytes);
+        if value < MODULUS {
+            Some(Self(value))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the canonical representative as 16 lowercase hexadecimal
+    /// ASCII digits, zero-padded and with the most significant digit first.
+    ///
+    /// This is the same as formatting using `{:016x}`, but works
+    /// without an allocator and in constant contexts.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let hex = M61::from(0xabcu32).to_hex61();
+    /// assert_eq!(&hex, b"0000000000000abc");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn to_hex61(self) -> [u8; 16] {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let mut result = [0u8; 16];
+        let mut i = 0;
+        while i < 16 {
+            result[i] = DIGITS[((self.0 >> (60 - 4 * i)) & 0xf) as usize];
+            i += 1;
+        }
+        result
+    }
+
+    /// Returns the digits of the canonical representative in base `base`,
+    /// with the most significant digit first. The digits are stored in
+    /// `digits[..len]` of the returned `(digits, len)`, as values in
+    /// `0..base`. Zero consists of a single zero digit.
+    ///
+    /// Since the representative has at most 61 bits, 61 digits always
+    /// suffice. The base is limited to `2..=36`, which allows converting
+    /// the digits to characters using [`char::from_digit`].
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let (digits, len) = M61::from(1295u32).to_base_digits(36);
+    /// assert_eq!(&digits[..len], [35, 35]);
+    ///
+    /// let text: String = digits[..len]
+    ///     .iter()
+    ///     .map(|&d| char::from_digit(d as u32, 36).unwrap())
+    ///     .collect();
+    /// assert_eq!(text, "zz");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is not in the range `2..=36`.
+    #[must_use]
+    #[track_caller]
+    pub fn to_base_digits(self, base: u32) -> ([u8; 61], usize) {
+        assert!(
+            (2..=36).contains(&base),
+            "base must be in the range 2..=36, got {base}"
+        );
+
+        // The digits are written from the least significant one backwards,
+        // and moved to the front afterwards.
+        let mut digits = [0u8; 61];
+        let mut start = digits.len();
+        let mut x = self.0;
+        loop {
+            start -= 1;
+            digits[start] = (x % base as u64) as u8;
+            x /= base as u64;
+            if x == 0 {
+                break;
+            }
+        }
+
+        let len = digits.len() - start;
+        digits.copy_within(start.., 0);
+        (digits, len)
+    }
+
+    /// Converts the canonical representative into a floating-point number.
+    ///
+    /// The representative has up to 61 significant bits, while `f64`
+    /// only has 53, which means that the result is rounded to the nearest
+    /// representable value for representatives larger than `2^53`.
+    #[inline]
+    #[must_use]
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64
+    }
+
+    /// Returns the canonical representative divided by the modulus,
+    /// a number in the range `[0, 1)`.
+    ///
+    /// Like for [`M61::as_f64`], the result is not exact. Dividing the
+    /// rounded representative by the rounded modulus could result in `1.0`,
+    /// which is why the ratio is calculated with respect to `2^61` instead,
+    /// using only the 53 most significant bits. This introduces an absolute
+    /// error below `2^-52`, which is irrelevant for statistical purposes.
+    #[inline]
+    #[must_use]
+    pub fn to_ratio(self) -> f64 {
+        // Exact, since both factors are powers of two times a value below `2^53`.
+        (self.0 >> 8) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Evaluates a number given by its `digits` in an arbitrary `base`,
+    /// e.g. `10^9` for bignums with decimal limbs.
+    ///
+    /// The digits are stored least-significant first, meaning that the
+    /// result is `digits[0] + digits[1] * base + digits[2] * base^2 + ...`.
+    /// Both the digits and the base may take arbitrary `u64` values;
+    /// the digits are not required to be smaller than `base`.
+    /// For power-of-two bases, the [`M61Reduction`](crate::M61Reduction)
+    /// trait is considerably faster.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let x = M61::from_digits(&[123_456_789, 987_654_321, 42], 1_000_000_000);
+    /// assert_eq!(x, M61::from(42_987_654_321_123_456_789u128));
+    /// ```
+    #[must_use]
+    pub fn from_digits(digits: &[u64], base: u64) -> Self {
+        let base = Self::from(base);
+        digits
+            .iter()
+            .rev()
+            .fold(Self(0), |acc, &digit| acc * base + Self::from(digit))
+    }
+
+    /// Multiplies the value by a small integer.
+    ///
+    /// For `k < 8`, the product of the canonical representative and `k`
+    /// fits into 64 bits, which means that it can be reduced with a single
+    /// shift and mask instead of a 128-bit multiplication. Larger values
+    /// of `k` fall back to the general multiplication.
+    #[inline]
+    #[must_use]
+    pub fn mul_small(self, k: u32) -> Self {
+        if k < 8 {
+            Self::from(self.0 * k as u64)
+        } else {
+            self * Self::from(k)
+        }
+    }
+
+    /// Calculates `self^exp` using binary exponentiation.
+    /// By convention, `0^0` is one.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// assert_eq!(M61::from(3u32).pow(4), M61::from(81u32));
+    /// assert_eq!(M61::from(2u32).pow(61), M61::ONE);
+    /// assert_eq!(M61::ZERO.pow(0), M61::ONE);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn pow(mut self, mut exp: u64) -> Self {
+        let mut result = Self(1);
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result *= self;
+            }
+            self *= self;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Calculates `self^exp`, reducing the exponent modulo `2^61 - 2` first.
+    ///
+    /// The nonzero values form a multiplicative group of order `2^61 - 2`,
+    /// which means that `a^(2^61 - 2) = 1` for every nonzero `a` by Fermat's
+    /// little theorem. Exponents can thus be taken modulo the group order
+    /// without changing the result. For a zero base, the usual conventions
+    /// apply: `0^0 = 1` and `0^k = 0` for `k > 0`.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let a = M61::from(3u32);
+    /// assert_eq!(a.pow_group((1 << 61) - 2), M61::ONE);
+    /// assert_eq!(M61::ZERO.pow_group(0), M61::ONE);
+    /// assert_eq!(M61::ZERO.pow_group(5), M61::ZERO);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn pow_group(self, exp: u64) -> Self {
+        if self == Self::ZERO {
+            // The reduction of the exponent is only valid within the
+            // group, which zero isn't part of.
+            if exp == 0 {
+                Self::ONE
+            } else {
+                Self::ZERO
+            }
+        } else {
+            self.pow(exp % (MODULUS - 1))
+        }
+    }
+
+    /// Calculates the multiplicative inverse, or returns `None` for zero,
+    /// which doesn't have one. Unlike division, this never panics.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let x = M61::from(12345u32);
+    /// assert_eq!(x * x.inverse().unwrap(), M61::ONE);
+    /// assert_eq!(M61::ZERO.inverse(), None);
+    /// ```
+    #[must_use]
+    pub fn inverse(self) -> Option<Self> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        // Calculate the multiplicative inverse
+        // using the extended Euclidean algorithm.
+        // (https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm)
+
+        let b = self.0;
+        let mut r0 = MODULUS;
+        let mut r1 = b;
+        let mut s0 = 1i64;
+        let mut s1 = 0i64;
+        let mut t0 = 0i64;
+        let mut t1 = 1i64;
+
+        while r1 != 0 {
+            let (q, rn) = (r0 / r1, r0 % r1);
+            let sn = s0 - q as i64 * s1;
+            let tn = t0 - q as i64 * t1;
+
+            r0 = r1;
+            r1 = rn;
+            s0 = s1;
+            s1 = sn;
+            t0 = t1;
+            t1 = tn;
+        }
+
+        debug_assert_eq!(MODULUS as i128 * s0 as i128 + b as i128 * t0 as i128, 1);
+
+        Some(Self::from(t0))
+    }
+
+    /// Calculates `self / rhs`, or returns `None` if `rhs` is zero
+    /// instead of panicking like the [`ops::Div`] implementation.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let x = M61::from(42u32);
+    /// assert_eq!(x.checked_div(M61::from(6u32)), Some(M61::from(7u32)));
+    /// assert_eq!(x.checked_div(M61::ZERO), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        rhs.inverse().map(|inverse| self * inverse)
+    }
+
+    /// Calculates the Legendre symbol using Euler's criterion
+    /// `self^((2^61 - 2) / 2)`. The result is `0` for zero, `1` for
+    /// nonzero quadratic residues, and `-1` for quadratic non-residues.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// assert_eq!(M61::ZERO.legendre(), 0);
+    /// assert_eq!(M61::from(4u32).legendre(), 1);
+    /// assert_eq!(M61::from(3u32).legendre(), -1);
+    /// ```
+    #[must_use]
+    pub fn legendre(self) -> i8 {
+        match self.pow((MODULUS - 1) / 2) {
+            Self::ZERO => 0,
+            Self::ONE => 1,
+            _ => -1,
+        }
+    }
+
+    /// Returns whether `self` is a square, i.e. whether [`M61::sqrt`] returns
+    /// a root. Zero is considered a quadratic residue, being the square of itself.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// assert!(M61::from(4u32).is_quadratic_residue());
+    /// assert!(!M61::from(3u32).is_quadratic_residue());
+    /// assert!(M61::ZERO.is_quadratic_residue());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_quadratic_residue(self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Calculates a square root, i.e. a value `r` with `r * r == self`,
+    /// or returns `None` if there is none. Of the two roots `r` and `-r`,
+    /// the one with the smaller canonical representative is returned.
+    ///
+    /// Since `2^61 - 1 = 3 (mod 4)`, a root of a quadratic residue `a`
+    /// is given by `a^((2^61 - 1 + 1) / 4) = a^(2^59)`, which only
+    /// requires squaring, without needing the Tonelli-Shanks algorithm.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// assert_eq!(M61::from(9u32).sqrt(), Some(M61::from(3u32)));
+    /// assert_eq!(M61::ZERO.sqrt(), Some(M61::ZERO));
+    /// // `-1` isn't a square, since `2^61 - 1 = 3 (mod 4)`.
+    /// assert_eq!((-M61::ONE).sqrt(), None);
+    /// ```
+    #[must_use]
+    pub fn sqrt(self) -> Option<Self> {
+        let mut root = self;
+        for _ in 0..59 {
+            root *= root;
+        }
+
+        if root * root != self {
+            return None;
+        }
+
+        Some(root.min(-root))
+    }
+
+    /// Reinterprets a slice of values as a slice of their
+    /// canonical representatives without copying.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_u64_slice(s: &[M61]) -> &[u64] {
+        // SAFETY: `M61` is `#[repr(transparent)]` over `u64`.
+        unsafe { core::slice::from_raw_parts(s.as_ptr().cast::<u64>(), s.len()) }
+    }
+
+    /// Reinterprets a slice of integers as a slice of values without copying.
+    ///
+    /// Every integer must be smaller than `2^61 - 1`. This is only
+    /// checked if debug assertions are enabled. Otherwise, passing
+    /// larger integers does not lead to undefined behavior, but
+    /// arithmetic on the resulting values produces unspecified results.
+    ///
+    /// # Panics
+    ///
+    /// Panics if debug assertions are enabled and an integer
+    /// is not smaller than `2^61 - 1`.
+    #[inline]
+    #[must_use]
+    pub fn from_u64_slice(s: &[u64]) -> &[M61] {
+        debug_assert!(
+            s.iter().all(|&x| x < MODULUS),
+            "slice contains non-canonical values"
+        );
+
+        // SAFETY: `M61` is `#[repr(transparent)]` over `u64`.
+        unsafe { core::slice::from_raw_parts(s.as_ptr().cast::<M61>(), s.len()) }
+    }
+
+    /// Adds two values, returning both the unreduced sum of the
+    /// contained integers and the reduced result.
+    ///
+    /// Since both operands are smaller than `2^61 - 1`,
+    /// the unreduced sum is always smaller than `2^62`.
+    #[inline]
+    #[must_use]
+    pub fn add_wide(self, rhs: Self) -> (u64, Self) {
+        (self.0 + rhs.0, self + rhs)
+    }
+
+    /// Multiplies two values, returning both the unreduced 128-bit
+    /// product of the contained integers and the reduced result.
+    ///
+    /// Since both operands are smaller than `2^61 - 1`,
+    /// the unreduced product is always smaller than `2^122`.
+    #[inline]
+    #[must_use]
+    pub fn mul_wide(self, rhs: Self) -> (u128, Self) {
+        (self.0 as u128 * rhs.0 as u128, self * rhs)
+    }
 }
 
 /// Helper macro for the quick generation
@@ -53,8 +712,25 @@ macro_rules! make_fmt_impl {
     };
 }
 
-make_fmt_impl!(Display);
-make_fmt_impl!(Debug);
+/// Helper macro for the generation of formatting trait implementations
+/// which append the modulus if the alternate flag is given.
+macro_rules! make_fmt_impl_with_modulus {
+    ($trait:ident) => {
+        impl fmt::$trait for M61 {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                <u64 as fmt::$trait>::fmt(&self.0, f)?;
+                if f.alternate() {
+                    f.write_str(" (mod 2^61-1)")?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+make_fmt_impl_with_modulus!(Display);
+make_fmt_impl_with_modulus!(Debug);
 make_fmt_impl!(LowerExp);
 make_fmt_impl!(UpperExp);
 make_fmt_impl!(LowerHex);
@@ -69,7 +745,6 @@ macro_rules! make_trivial_from {
     ($type:ty) => {
         impl From<$type> for M61 {
             #[inline(always)]
-            #[must_use]
             fn from(value: $type) -> Self {
                 // rustc warns us against this seemingly
                 // useless comparison whenever the argument is
@@ -95,7 +770,6 @@ make_trivial_from!(usize);
 #[cfg(target_pointer_width = "64")]
 impl From<usize> for M61 {
     #[inline(always)]
-    #[must_use]
     fn from(value: usize) -> Self {
         Self::from(value as u64)
     }
@@ -110,7 +784,6 @@ make_trivial_from!(isize);
 #[cfg(target_pointer_width = "64")]
 impl From<isize> for M61 {
     #[inline(always)]
-    #[must_use]
     fn from(value: isize) -> Self {
         Self::from(value as i64)
     }
@@ -118,20 +791,13 @@ impl From<isize> for M61 {
 
 impl From<u64> for M61 {
     #[inline]
-    #[must_use]
     fn from(value: u64) -> Self {
-        let tmp = (value & MODULUS) + (value >> 61);
-        if tmp >= MODULUS {
-            Self(tmp - MODULUS)
-        } else {
-            Self(tmp)
-        }
+        Self::from_u64_const(value)
     }
 }
 
 impl From<i64> for M61 {
     #[inline]
-    #[must_use]
     fn from(mut value: i64) -> Self {
         if value < 0 {
             value = value.wrapping_add(4 * MODULUS as i64);
@@ -146,8 +812,10 @@ impl From<i64> for M61 {
 
 impl From<u128> for M61 {
     #[inline]
-    #[must_use]
     fn from(value: u128) -> Self {
+        // The sum of the three digits base `2^61` is at most
+        // `2 * (2^61 - 1) + 63 < 2^63`. A single digit sum of it is
+        // at most `2^61 + 2`, which the conversion from `u64` handles.
         let mut x = value as u64 & MODULUS;
         x += (value >> 61) as u64 & MODULUS;
         x += (value >> 122) as u64;
@@ -157,13 +825,42 @@ impl From<u128> for M61 {
 
 impl From<i128> for M61 {
     #[inline]
-    #[must_use]
-    fn from(mut value: i128) -> Self {
-        while value < 0 {
-            value += 16 * ((1 << 122) - 1);
+    fn from(value: i128) -> Self {
+        // Reducing the magnitude avoids any overflow, as
+        // `unsigned_abs` is well-defined even for `i128::MIN`.
+        let magnitude = Self::from(value.unsigned_abs());
+        if value < 0 {
+            Self(0) - magnitude
+        } else {
+            magnitude
         }
+    }
+}
 
-        Self::from(value as u128)
+/// Reduces the array as a little-endian number, like
+/// [`reduce_m61`](crate::M61Reduction::reduce_m61) does for slices.
+///
+/// Unlike [`M61::from_le_bytes`], this accepts arbitrary input.
+impl<const N: usize> From<[u8; N]> for M61 {
+    #[inline]
+    fn from(value: [u8; N]) -> Self {
+        crate::M61Reduction::reduce_m61(&value[..])
+    }
+}
+
+impl PartialEq<u64> for M61 {
+    /// Compares against the reduction of `other`,
+    /// so that `M61::from(x) == x` holds for every `x`.
+    #[inline(always)]
+    fn eq(&self, other: &u64) -> bool {
+        *self == Self::from(*other)
+    }
+}
+
+impl PartialEq<M61> for u64 {
+    #[inline(always)]
+    fn eq(&self, other: &M61) -> bool {
+        other == self
     }
 }
 
@@ -175,7 +872,6 @@ macro_rules! make_arith_impl {
             type Output = Self;
 
             #[inline]
-            #[must_use]
             fn $func(self, rhs: Self) -> Self::Output {
                 #[allow(clippy::redundant_closure_call)]
                 Self($impl(self.0, rhs.0))
@@ -186,12 +882,29 @@ macro_rules! make_arith_impl {
             type Output = Self;
 
             #[inline(always)]
-            #[must_use]
             fn $func(self, rhs: &Self) -> Self::Output {
                 self $op *rhs
             }
         }
 
+        impl<'a> ops::$trait<M61> for &'a M61 {
+            type Output = M61;
+
+            #[inline(always)]
+            fn $func(self, rhs: M61) -> Self::Output {
+                *self $op rhs
+            }
+        }
+
+        impl<'a, 'b> ops::$trait<&'b M61> for &'a M61 {
+            type Output = M61;
+
+            #[inline(always)]
+            fn $func(self, rhs: &'b M61) -> Self::Output {
+                *self $op *rhs
+            }
+        }
+
         impl ops::$trait_assign for M61 {
             #[inline(always)]
             fn $func_assign(&mut self, rhs: Self) {
@@ -209,73 +922,89 @@ macro_rules! make_arith_impl {
 }
 
 make_arith_impl!(Add, AddAssign, add, add_assign, +, |a, b| {
-    let x = a + b;
-    if x >= MODULUS {
-        x - MODULUS
-    } else {
-        x
-    }
+    Self::add_const(Self(a), Self(b)).0
 });
 make_arith_impl!(Sub, SubAssign, sub, sub_assign, -, |a, b| {
-    let x = a + MODULUS - b;
-    if x >= MODULUS {
-        x - MODULUS
-    } else {
-        x
-    }
+    Self::sub_const(Self(a), Self(b)).0
 });
 make_arith_impl!(Mul, MulAssign, mul, mul_assign, *, |a, b| {
-    let x = a as u128 * b as u128;
-    let mut hi = (x >> 61) as u64;
-    let mut lo = (x as u64) & MODULUS;
-    lo = lo.wrapping_add(hi);
-    hi = lo.wrapping_sub(MODULUS);
-    if lo < MODULUS {
-        lo
-    } else {
-        hi
-    }
+    Self::mul_const(Self(a), Self(b)).0
 });
 make_arith_impl!(Div, DivAssign, div, div_assign, /, |a, b| {
-    if b == 0 {
-        panic!("attempt to divide by zero");
+    match Self(b).inverse() {
+        Some(inverse) => (Self(a) * inverse).0,
+        None => panic!("attempt to divide by zero"),
     }
+});
 
-    // Calculate the multiplicative inverse
-    // using the extended Euclidean algorithm.
-    // (https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm)
+/// Helper macro for the implementation of arithmetic
+/// operators where one operand is a `u64`.
+/// The `u64` operand is reduced before the operation.
+macro_rules! make_mixed_arith_impl {
+    ($trait:ident, $trait_assign:ident, $func:ident, $func_assign:ident, $op:tt) => {
+        impl ops::$trait<u64> for M61 {
+            type Output = Self;
 
-    let mut r0 = MODULUS;
-    let mut r1 = b;
-    let mut s0 = 1i64;
-    let mut s1 = 0i64;
-    let mut t0 = 0i64;
-    let mut t1 = 1i64;
+            #[inline(always)]
+            fn $func(self, rhs: u64) -> Self::Output {
+                self $op Self::from(rhs)
+            }
+        }
 
-    while r1 != 0 {
-        let (q, rn) = (r0 / r1, r0 % r1);
-        let sn = s0 - q as i64 * s1;
-        let tn = t0 - q as i64 * t1;
+        impl ops::$trait<M61> for u64 {
+            type Output = M61;
 
-        r0 = r1;
-        r1 = rn;
-        s0 = s1;
-        s1 = sn;
-        t0 = t1;
-        t1 = tn;
-    }
+            #[inline(always)]
+            fn $func(self, rhs: M61) -> Self::Output {
+                M61::from(self) $op rhs
+            }
+        }
 
-    debug_assert_eq!(MODULUS as i128 * s0 as i128 + b as i128 * t0 as i128, 1);
+        impl ops::$trait_assign<u64> for M61 {
+            #[inline(always)]
+            fn $func_assign(&mut self, rhs: u64) {
+                *self = *self $op rhs
+            }
+        }
+    };
+}
+
+make_mixed_arith_impl!(Add, AddAssign, add, add_assign, +);
+make_mixed_arith_impl!(Sub, SubAssign, sub, sub_assign, -);
+make_mixed_arith_impl!(Mul, MulAssign, mul, mul_assign, *);
 
-    (Self(a) * Self::from(t0)).0
+make_arith_impl!(Rem, RemAssign, rem, rem_assign, %, |a, b| {
+    if b == 0 {
+        panic!("attempt to calculate the remainder with a divisor of zero");
+    }
+
+    a % b
 });
-//make_arith_impl!(Rem, RemAssign, rem, rem_assign, %, |a, b| {
-//    a % b
-//});
+
+impl ops::Neg for M61 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 {
+            self
+        } else {
+            Self(MODULUS - self.0)
+        }
+    }
+}
+
+impl ops::Neg for &M61 {
+    type Output = M61;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        -*self
+    }
+}
 
 impl iter::Sum for M61 {
     #[inline(always)]
-    #[must_use]
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self(0), |a, b| a + b)
     }
@@ -283,7 +1012,6 @@ impl iter::Sum for M61 {
 
 impl<'a> iter::Sum<&'a M61> for M61 {
     #[inline(always)]
-    #[must_use]
     fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
         iter.fold(Self(0), |a, b| a + b)
     }
@@ -291,7 +1019,6 @@ impl<'a> iter::Sum<&'a M61> for M61 {
 
 impl iter::Product for M61 {
     #[inline(always)]
-    #[must_use]
     fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self(1), |a, b| a * b)
     }
@@ -299,16 +1026,502 @@ impl iter::Product for M61 {
 
 impl<'a> iter::Product<&'a M61> for M61 {
     #[inline(always)]
-    #[must_use]
     fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
         iter.fold(Self(1), |a, b| a * b)
     }
 }
 
+#[cfg(feature = "num-traits")]
+impl num_traits::Zero for M61 {
+    #[inline(always)]
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        *self == Self(0)
+    }
+}
+
+#[cfg(feature = "num-traits")]
+impl num_traits::One for M61 {
+    #[inline(always)]
+    fn one() -> Self {
+        Self(1)
+    }
+}
+
+/// Panics if `self` is zero, like the [`ops::Div`] implementation.
+/// Use [`M61::inverse`] for a non-panicking version.
+#[cfg(feature = "num-traits")]
+impl num_traits::Inv for M61 {
+    type Output = Self;
+
+    #[inline]
+    fn inv(self) -> Self::Output {
+        match self.inverse() {
+            Some(inverse) => inverse,
+            None => panic!("attempt to invert zero"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::final_reduction;
     use super::M61;
     use super::MODULUS;
+    use super::{shift_exponent, POW2_MOD};
+
+    #[test]
+    fn from_byte_array() {
+        use crate::reference::reference_reduce;
+
+        fn check<const N: usize>() {
+            let mut bytes = [0u8; N];
+            for (i, b) in bytes.iter_mut().enumerate() {
+                *b = (i as u8).wrapping_mul(97).wrapping_add(13);
+            }
+            assert_eq!(M61::from(bytes), reference_reduce(&bytes));
+            assert_eq!(M61::from([u8::MAX; N]), reference_reduce(&[u8::MAX; N]));
+        }
+
+        check::<0>();
+        check::<1>();
+        check::<8>();
+        check::<32>();
+        check::<64>();
+    }
+
+    #[test]
+    fn raw_roundtrip() {
+        for x in [0, 1, 42, MODULUS - 1] {
+            let y = M61::from_raw(x);
+            assert!(y.is_canonical());
+            assert_eq!(y.into_raw(), x);
+            assert_eq!(y, M61::from(x));
+        }
+
+        const ONE: M61 = M61::from_raw(1);
+        assert_eq!(ONE.into_raw(), 1);
+        assert!(!M61(MODULUS).is_canonical());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn from_raw_rejects_non_canonical() {
+        let _ = M61::from_raw(MODULUS);
+    }
+
+    quickcheck::quickcheck! {
+        fn reference_arith(a: u64, b: u64) -> bool {
+            let (a, b) = (M61::from(a), M61::from(b));
+            let (ra, rb) = (&a, &b);
+
+            let div = b.0 == 0 || [ra / b, ra / rb, a / rb] == [a / b; 3];
+            div && [ra + b, ra + rb, a + rb] == [a + b; 3]
+                && [ra - b, ra - rb, a - rb] == [a - b; 3]
+                && [ra * b, ra * rb, a * rb] == [a * b; 3]
+        }
+    }
+
+    #[test]
+    fn reference_arith_in_iterators() {
+        let v: Vec<M61> = (1..100u32).map(M61::from).collect();
+        let sums: Vec<M61> = v.iter().zip(&v[1..]).map(|(a, b)| a + b).collect();
+        let products: Vec<M61> = v.iter().zip(&v).map(|(a, b)| a * b).collect();
+
+        assert_eq!(sums[10], M61(23));
+        assert_eq!(products[10], M61(121));
+    }
+
+    #[test]
+    fn from_i128_boundaries() {
+        let period = 16 * ((1i128 << 122) - 1);
+        for value in [
+            i128::MIN,
+            i128::MIN + 1,
+            -period - 1,
+            -period,
+            -(MODULUS as i128),
+            -1,
+            0,
+            1,
+            i128::MAX,
+        ] {
+            let expected = M61(value.rem_euclid(MODULUS as i128) as u64);
+            assert_eq!(M61::from(value), expected, "value: {value}");
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn from_i128_correct(value: i128) -> bool {
+            M61::from(value) == M61(value.rem_euclid(MODULUS as i128) as u64)
+        }
+    }
+
+    #[test]
+    fn float_conversions() {
+        assert_eq!(M61(0).as_f64(), 0.0);
+        assert_eq!(M61(12345).as_f64(), 12345.0);
+        assert_eq!(M61(MODULUS - 1).as_f64(), (MODULUS - 1) as f64);
+
+        assert_eq!(M61(0).to_ratio(), 0.0);
+        assert!((M61(MODULUS / 2).to_ratio() - 0.5).abs() < 1e-15);
+        assert!(M61(MODULUS - 1).to_ratio() < 1.0);
+    }
+
+    quickcheck::quickcheck! {
+        fn to_ratio_in_range(x: u64) -> bool {
+            let x = M61::from(x);
+            let ratio = x.to_ratio();
+            (0.0..1.0).contains(&ratio) && (ratio - x.as_f64() / MODULUS as f64).abs() < 1e-15
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn pow_group_matches_pow(x: u64, exp: u64) -> bool {
+            let x = M61::from(x);
+            x.pow_group(exp) == x.pow(exp)
+        }
+
+        fn pow_group_fermat(x: u64, k: u8) -> bool {
+            let x = M61::from(x);
+            let order = MODULUS - 1;
+            x == M61::ZERO || x.pow_group(order * (k as u64 % 8)) == M61::ONE
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn inverse_correct(x: u64) -> bool {
+            let x = M61::from(x);
+            match x.inverse() {
+                Some(y) => x * y == M61::ONE && M61::ONE / x == y,
+                None => x == M61::ZERO,
+            }
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn checked_div_matches_div(a: u64, b: u64) -> bool {
+            let (a, b) = (M61::from(a), M61::from(b));
+            if b == M61::ZERO {
+                a.checked_div(b).is_none()
+            } else {
+                a.checked_div(b) == Some(a / b)
+            }
+        }
+    }
+
+    #[test]
+    fn checked_div_by_zero() {
+        assert_eq!(M61::ZERO.checked_div(M61::ZERO), None);
+        assert_eq!(M61(MODULUS - 1).checked_div(M61::ZERO), None);
+        assert_eq!(M61::ZERO.checked_div(M61::ONE), Some(M61::ZERO));
+    }
+
+    #[test]
+    fn inverse_bounds() {
+        assert_eq!(M61::ZERO.inverse(), None);
+        assert_eq!(M61::ONE.inverse(), Some(M61::ONE));
+        assert_eq!(M61(MODULUS - 1).inverse(), Some(M61(MODULUS - 1)));
+
+        for x in (1..1000).chain(MODULUS - 1000..MODULUS) {
+            assert_eq!(M61(x) * M61(x).inverse().unwrap(), M61::ONE);
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn sqrt_of_square(x: u64) -> bool {
+            let x = M61::from(x);
+            let root = (x * x).sqrt();
+            root == Some(x.min(-x))
+        }
+
+        fn sqrt_correct(x: u64) -> bool {
+            let x = M61::from(x);
+            match x.sqrt() {
+                Some(root) => root * root == x && root <= -root,
+                None => (-x).sqrt().is_some(),
+            }
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn legendre_of_squares(x: u64) -> bool {
+            // Since `-1` is a non-residue, exactly one of `x^2` and
+            // `-x^2` is a residue for nonzero `x`.
+            let square = M61::from(x) * M61::from(x);
+            if square == M61::ZERO {
+                return square.legendre() == 0;
+            }
+
+            square.legendre() == 1 && (-square).legendre() == -1
+        }
+
+        fn legendre_multiplicative(a: u64, b: u64) -> bool {
+            let (a, b) = (M61::from(a), M61::from(b));
+            (a * b).legendre() == a.legendre() * b.legendre()
+        }
+
+        fn is_quadratic_residue_matches_sqrt(x: u64) -> bool {
+            let x = M61::from(x);
+            x.is_quadratic_residue() == x.sqrt().is_some()
+        }
+    }
+
+    #[test]
+    fn legendre_small_values() {
+        assert_eq!(M61::ZERO.legendre(), 0);
+        for x in 1..1000u64 {
+            assert_eq!(M61(x * x).legendre(), 1);
+            assert_eq!(M61(MODULUS - x * x).legendre(), -1);
+        }
+
+        // `2` is a residue since `2^61 - 1 = 7 (mod 8)`, while `3` isn't
+        // by quadratic reciprocity, since `2^61 - 1 = 1 (mod 3)`.
+        assert_eq!(M61(2).legendre(), 1);
+        assert_eq!(M61(3).legendre(), -1);
+    }
+
+    #[test]
+    fn sqrt_bounds() {
+        assert_eq!(M61::ZERO.sqrt(), Some(M61::ZERO));
+        assert_eq!(M61::ONE.sqrt(), Some(M61::ONE));
+        assert_eq!(M61(4).sqrt(), Some(M61(2)));
+        // Both `3` and `-1` are quadratic non-residues.
+        assert_eq!(M61(3).sqrt(), None);
+        assert_eq!(M61(MODULUS - 1).sqrt(), None);
+    }
+
+    #[test]
+    fn const_arithmetic() {
+        const X: M61 = M61::from_u64_const(u64::MAX)
+            .mul_const(M61::from_u64_const(3))
+            .add_const(M61::ONE)
+            .sub_const(M61::from_u64_const(5));
+
+        let y = const { M61::from_u64_const(MODULUS).add_const(M61(MODULUS - 1)) };
+
+        assert_eq!(X, M61::from(u64::MAX) * M61(3) + M61(1) - M61(5));
+        assert_eq!(y, M61(MODULUS - 1));
+    }
+
+    quickcheck::quickcheck! {
+        fn const_arithmetic_matches_operators(a: u64, b: u64) -> bool {
+            let (x, y) = (M61::from_u64_const(a), M61::from_u64_const(b));
+            x == M61::from(a)
+                && x.add_const(y) == x + y
+                && x.sub_const(y) == x - y
+                && x.mul_const(y) == x * y
+        }
+    }
+
+    #[test]
+    fn rem_of_representatives() {
+        assert_eq!(M61(17) % M61(5), M61(2));
+        assert_eq!(M61(5) % M61(17), M61(5));
+        assert_eq!(M61(MODULUS - 1) % M61(1 << 60), M61((1 << 60) - 2));
+        assert_eq!(M61::ZERO % M61(3), M61::ZERO);
+
+        let mut x = M61(100);
+        x %= &M61(7);
+        assert_eq!(x, M61(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "divisor of zero")]
+    fn rem_by_zero() {
+        let _ = M61(5) % M61::ZERO;
+    }
+
+    quickcheck::quickcheck! {
+        fn rem_matches_u64(a: u64, b: u64) -> bool {
+            let (a, b) = (M61::from(a), M61::from(b));
+            b == M61::ZERO || (a % b).get() == a.get() % b.get()
+        }
+    }
+
+    #[test]
+    fn neg_bounds() {
+        assert_eq!(-M61::ZERO, M61::ZERO);
+        assert_eq!(-M61::ONE, M61(MODULUS - 1));
+        assert_eq!(-M61(MODULUS - 1), M61::ONE);
+        assert_eq!(-&M61(5), M61(MODULUS - 5));
+
+        for x in (0..1000).chain(MODULUS - 1000..MODULUS) {
+            assert_eq!(-M61(x) + M61(x), M61::ZERO);
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn neg_is_additive_inverse(x: u64) -> bool {
+            let x = M61::from(x);
+            let y = -x;
+            y.0 < MODULUS && y + x == M61::ZERO && y == M61::ZERO - x && -y == x
+        }
+    }
+
+    #[test]
+    fn pow_matches_repeated_multiplication() {
+        for base in [0, 1, 2, 3, 12345, MODULUS - 1] {
+            let base = M61(base);
+            let mut expected = M61::ONE;
+            for exp in 0..100 {
+                assert_eq!(base.pow(exp), expected, "{base}^{exp}");
+                expected *= base;
+            }
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn pow_fermat(x: u64) -> bool {
+            let x = M61::from(x);
+            x == M61::ZERO || x.pow(MODULUS - 1) == M61::ONE
+        }
+    }
+
+    #[test]
+    fn pow_group_zero() {
+        assert_eq!(M61::ZERO.pow_group(0), M61::ONE);
+        assert_eq!(M61::ZERO.pow_group(1), M61::ZERO);
+        assert_eq!(M61::ZERO.pow_group(MODULUS - 1), M61::ZERO);
+        assert_eq!(M61(MODULUS).pow_group(u64::MAX), M61::ZERO);
+    }
+
+    quickcheck::quickcheck! {
+        fn to_hex61_matches_format(x: u64) -> bool {
+            let x = M61::from(x);
+            x.to_hex61()[..] == *format!("{:016x}", x).as_bytes()
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn to_base_digits_roundtrip(x: u64, base: u32) -> bool {
+            let x = M61::from(x);
+            let base = base % 35 + 2;
+            let (digits, len) = x.to_base_digits(base);
+
+            let value = digits[..len]
+                .iter()
+                .fold(0u64, |acc, &d| acc * base as u64 + d as u64);
+            value == x.get() && (len == 1 || digits[0] != 0)
+        }
+    }
+
+    #[test]
+    fn to_base_digits_bounds() {
+        let (digits, len) = M61::ZERO.to_base_digits(10);
+        assert_eq!(&digits[..len], [0]);
+
+        let (digits, len) = M61(MODULUS - 1).to_base_digits(2);
+        assert_eq!(len, 61);
+        assert_eq!(digits[60], 0);
+        assert!(digits[..60].iter().all(|&d| d == 1));
+
+        let (digits, len) = M61(MODULUS - 1).to_base_digits(36);
+        let text: String = digits[..len]
+            .iter()
+            .map(|&d| char::from_digit(d as u32, 36).unwrap())
+            .collect();
+        assert_eq!(u64::from_str_radix(&text, 36), Ok(MODULUS - 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "base must be in the range 2..=36")]
+    fn to_base_digits_invalid_base() {
+        let _ = M61::ONE.to_base_digits(37);
+    }
+
+    #[test]
+    fn to_hex61_bounds() {
+        assert_eq!(&M61::ZERO.to_hex61(), b"0000000000000000");
+        assert_eq!(&M61(MODULUS - 1).to_hex61(), b"1ffffffffffffffe");
+    }
+
+    #[test]
+    fn pow2_mod_correct() {
+        let mut expected = M61(1);
+        for k in 0..200 {
+            assert_eq!(POW2_MOD[k % 61], expected);
+            expected += expected;
+        }
+    }
+
+    #[test]
+    fn shift_exponent_no_overflow() {
+        // The products of these lengths with the number of bits
+        // overflow `usize`, so the expected values are computed in `u128`.
+        for len in [
+            usize::MAX,
+            usize::MAX / 2 + 1,
+            usize::MAX / 64 + 1,
+            usize::MAX / 61 * 7,
+        ] {
+            for bits in [8, 16, 32, 64, 128] {
+                let expected = (len as u128 * bits as u128 % 61) as usize;
+                assert_eq!(shift_exponent(len, bits), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_small_max() {
+        let x = M61(MODULUS - 1);
+        for k in 0..100 {
+            assert_eq!(x.mul_small(k), x * M61::from(k));
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn from_u64_slice_rejects_non_canonical() {
+        let _ = M61::from_u64_slice(&[0, MODULUS]);
+    }
+
+    #[test]
+    fn final_reduction_bounds() {
+        for x in [0, 1, MODULUS - 1, MODULUS, 2 * MODULUS, 3 * MODULUS - 1] {
+            assert_eq!(final_reduction(x).get(), x % MODULUS);
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "digit sum is not partially reduced")]
+    fn final_reduction_rejects_unreduced() {
+        let _ = final_reduction(3 * MODULUS);
+    }
+
+    #[test]
+    fn creation_u128_boundaries() {
+        let m = MODULUS as u128;
+        let mut values = vec![
+            u128::MAX,
+            u128::MAX - 1,
+            1 << 122,
+            (1 << 122) - 1,
+            (1 << 123) - 1,
+            (1 << 127) - 1,
+            1 << 127,
+            m * m,
+            m * m * 64 - 1,
+            m * m * 64,
+            u128::MAX - u128::MAX % m,
+        ];
+        for i in 0..128 {
+            values.push(1 << i);
+            values.push((1 << i) - 1);
+            values.push(u128::MAX >> i);
+        }
+
+        for x in values {
+            assert_eq!(M61::from(x).get(), (x % m) as u64, "input: {x:#x}");
+        }
+    }
 
     quickcheck::quickcheck! {
         fn creation_u64_correct(x: u64) -> bool {
@@ -364,5 +1577,133 @@ mod tests {
 
             expected == actual
         }
+
+        fn eq_u64_reduces(x: u64) -> bool {
+            let y = M61::from(x);
+            let z = M61::from(x ^ 1);
+
+            y == x && x % MODULUS == y && z != x
+        }
+
+        fn mixed_arith_correct(x: u64, y: u64) -> bool {
+            let (mx, my) = (M61::from(x), M61::from(y));
+
+            let mut z = mx;
+            z += y;
+            z *= y;
+            z -= y;
+
+            mx + y == mx + my
+                && x + my == mx + my
+                && mx - y == mx - my
+                && x - my == mx - my
+                && mx * y == mx * my
+                && x * my == mx * my
+                && z == (mx + my) * my - my
+                && mx * 3 + 1 == mx * M61::from(3u32) + M61::from(1u32)
+        }
+
+        fn min_max_repr_correct(x: u64, y: u64) -> bool {
+            let (x, y) = (M61::from(x), M61::from(y));
+
+            x.min_repr(y).get() == x.get().min(y.get())
+                && x.max_repr(y).get() == x.get().max(y.get())
+                && x.cmp_canonical(y) == x.cmp(&y)
+        }
+
+        fn fmt_alternate(x: u64) -> bool {
+            let y = M61::from(x);
+            format!("{y}") == format!("{}", y.get())
+                && format!("{y:?}") == format!("{}", y.get())
+                && format!("{y:#}") == format!("{} (mod 2^61-1)", y.get())
+                && format!("{y:#?}") == format!("{} (mod 2^61-1)", y.get())
+                && format!("{y:#x}") == format!("{:#x}", y.get())
+        }
+
+        fn add_wide_correct(a: u64, b: u64) -> bool {
+            let (a, b) = (M61::from(a), M61::from(b));
+            let (wide, reduced) = a.add_wide(b);
+            wide == a.get() + b.get() && M61::from(wide) == reduced && reduced == a + b
+        }
+
+        fn mul_wide_correct(a: u64, b: u64) -> bool {
+            let (a, b) = (M61::from(a), M61::from(b));
+            let (wide, reduced) = a.mul_wide(b);
+            wide == a.get() as u128 * b.get() as u128
+                && M61::from(wide) == reduced
+                && reduced == a * b
+        }
+
+        fn from_digits_power_of_two(v: Vec<u16>) -> bool {
+            let digits: Vec<u64> = v.iter().map(|&x| x as u64).collect();
+            M61::from_digits(&digits, 1 << 16) == crate::M61Reduction::reduce_m61(&v[..])
+        }
+
+        fn from_digits_correct(digits: Vec<u64>, base: u64) -> bool {
+            let mut power = M61(1);
+            let mut expected = M61(0);
+            for &d in &digits {
+                expected += M61::from(d) * power;
+                power *= M61::from(base);
+            }
+
+            M61::from_digits(&digits, base) == expected
+        }
+
+        fn mul_small_correct(x: u64, k: u32) -> bool {
+            let x = M61::from(x);
+            (0..8).chain([k]).all(|k| x.mul_small(k) == x * M61::from(k))
+        }
+
+        fn u64_slice_roundtrip(v: Vec<u64>) -> bool {
+            let values: Vec<M61> = v.into_iter().map(M61::from).collect();
+            let raw = M61::as_u64_slice(&values);
+
+            raw.iter().zip(&values).all(|(&x, y)| x == y.get())
+                && M61::from_u64_slice(raw) == &values[..]
+        }
+
+        fn le_bytes_roundtrip(x: u64) -> bool {
+            let x = M61::from(x);
+            M61::from_le_bytes(x.to_le_bytes()) == Some(x)
+        }
+
+        fn le_bytes_rejects_non_canonical(x: u64) -> bool {
+            let x = x | MODULUS;
+            M61::from_le_bytes(x.to_le_bytes()).is_none()
+        }
+    }
+
+    #[cfg(feature = "num-traits")]
+    mod num_traits_impls {
+        use super::*;
+        use num_traits::{Inv, One, Zero};
+
+        #[test]
+        fn identities() {
+            assert_eq!(<M61 as Zero>::zero(), M61::ZERO);
+            assert_eq!(<M61 as One>::one(), M61::ONE);
+            assert!(M61::ZERO.is_zero());
+            assert!(!M61::ONE.is_zero());
+            assert!(M61::ONE.is_one());
+        }
+
+        #[test]
+        #[should_panic(expected = "attempt to invert zero")]
+        fn inv_zero() {
+            let _ = M61::ZERO.inv();
+        }
+
+        quickcheck::quickcheck! {
+            fn generic_pow_matches(x: u64, exp: u16) -> bool {
+                let x = M61::from(x);
+                num_traits::pow(x, exp as usize) == x.pow(exp as u64)
+            }
+
+            fn inv_matches_inverse(x: u64) -> bool {
+                let x = M61::from(x);
+                x.is_zero() || x.inv() == x.inverse().unwrap()
+            }
+        }
     }
 }
@@ -0,0 +1,129 @@
+//! Scalar versions of the reduction functions using the generalized
+//! Horner's method, used for targets without a vectorized backend.
+//!
+//! The input is split into two polynomials in the same way the `simd` module
+//! does for architectures with two 64-bit lanes, but every lane is
+//! a plain `u64`. The two Horner chains are independent of each other,
+//! so superscalar CPUs can evaluate them in parallel, roughly halving the
+//! length of the dependency chain compared to [`crate::fallback`].
+//! No intrinsics are involved, making this portable to every target.
+
+use crate::definition::{final_reduction, M61, MODULUS};
+
+#[allow(unused_imports)]
+pub(crate) use crate::fallback::force_init;
+#[cfg(feature = "bench")]
+#[allow(unused_imports)]
+pub(crate) use crate::fallback::push_backends;
+
+crate::fallback::make_resolve! {
+    resolve_u8 => reduce_u8, u8;
+    resolve_u16 => reduce_u16, u16;
+    resolve_u32 => reduce_u32, u32;
+    resolve_u64 => reduce_u64, u64;
+}
+
+/// Calculates the reduction of the number whose least significant digits
+/// are yielded by `pairs`, followed by the most significant digits
+/// stored in `hi`, which don't fill an entire pair.
+#[inline(always)]
+fn reduction_core(pairs: impl DoubleEndedIterator<Item = [u64; 2]>, hi: [u64; 2]) -> M61 {
+    let [mut h0, mut h1] = hi;
+
+    // Both polynomials are evaluated at `2^128 = 2^6 (mod 2^61 - 1)`.
+    // Masking before shifting keeps the lanes below `2^62 + 2^9`
+    // for arbitrary inputs, so they don't need to be pre-reduced.
+    for [l0, l1] in pairs.rev() {
+        h0 = (l0 & MODULUS) + (l0 >> 61) + ((h0 & (MODULUS >> 6)) << 6) + (h0 >> 55);
+        h1 = (l1 & MODULUS) + (l1 >> 61) + ((h1 & (MODULUS >> 6)) << 6) + (h1 >> 55);
+    }
+
+    // The second polynomial is offset by `2^64 = 2^3 (mod 2^61 - 1)`.
+    final_reduction((h0 & MODULUS) + (h0 >> 61) + ((h1 & (MODULUS >> 3)) << 3) + (h1 >> 58))
+}
+
+/// Joins the `N` digits of `c`, each consisting of `64 / N` bits,
+/// into a single `u64` in little-endian ordering.
+#[inline(always)]
+fn join<T: Into<u64> + Copy, const N: usize>(c: &[T]) -> u64 {
+    let bits = 64 / N;
+    c[..N]
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, &x)| acc | (x.into() << (i * bits)))
+}
+
+/// Splits `s` into pairs of `u64` limbs consisting of `N` digits each,
+/// and reduces them, with the remaining digits forming the top pair.
+#[inline(always)]
+fn reduce<T: Into<u64> + Copy + Default, const N: usize>(s: &[T]) -> M61 {
+    let chunks = s.chunks_exact(2 * N);
+
+    let mut tail = [[T::default(); N]; 2];
+    for (i, &x) in chunks.remainder().iter().enumerate() {
+        tail[i / N][i % N] = x;
+    }
+
+    let pairs = chunks.map(|c| [join::<T, N>(&c[..N]), join::<T, N>(&c[N..])]);
+    reduction_core(pairs, [join::<T, N>(&tail[0]), join::<T, N>(&tail[1])])
+}
+
+pub(crate) fn reduce_u8(s: &[u8]) -> M61 {
+    reduce::<u8, 8>(s)
+}
+
+pub(crate) fn reduce_u16(s: &[u16]) -> M61 {
+    reduce::<u16, 4>(s)
+}
+
+pub(crate) fn reduce_u32(s: &[u32]) -> M61 {
+    reduce::<u32, 2>(s)
+}
+
+pub(crate) fn reduce_u64(s: &[u64]) -> M61 {
+    reduce::<u64, 1>(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::reference::reference_tests! {
+        reduce_u8_reference(u8) => reduce_u8;
+        reduce_u16_reference(u16) => reduce_u16;
+        reduce_u32_reference(u32) => reduce_u32;
+        reduce_u64_reference(u64) => reduce_u64;
+    }
+
+    #[test]
+    fn reduce_max() {
+        for len in 0..200 {
+            let bytes = vec![u8::MAX; len];
+            assert_eq!(reduce_u8(&bytes), crate::fallback::reduce_u8(&bytes));
+            let words = vec![u16::MAX; len];
+            assert_eq!(reduce_u16(&words), crate::fallback::reduce_u16(&words));
+            let dwords = vec![u32::MAX; len];
+            assert_eq!(reduce_u32(&dwords), crate::fallback::reduce_u32(&dwords));
+            let qwords = vec![u64::MAX; len];
+            assert_eq!(reduce_u64(&qwords), crate::fallback::reduce_u64(&qwords));
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_u8_correct(slice: Vec<u8>) -> bool {
+            reduce_u8(&slice) == crate::fallback::reduce_u8(&slice)
+        }
+
+        fn reduce_u16_correct(slice: Vec<u16>) -> bool {
+            reduce_u16(&slice) == crate::fallback::reduce_u16(&slice)
+        }
+
+        fn reduce_u32_correct(slice: Vec<u32>) -> bool {
+            reduce_u32(&slice) == crate::fallback::reduce_u32(&slice)
+        }
+
+        fn reduce_u64_correct(slice: Vec<u64>) -> bool {
+            reduce_u64(&slice) == crate::fallback::reduce_u64(&slice)
+        }
+    }
+}
@@ -0,0 +1,953 @@
+//! Free functions building on the [`M61`] type.
+
+use core::fmt;
+
+use crate::definition::{final_reduction, shift_exponent, M61, MODULUS, POW2_MOD};
+use crate::m31::{self, M31};
+
+/// Calculates the dot product `a_0 b_0 + a_1 b_1 + ...` modulo `2^61 - 1`.
+///
+/// Every element is reduced before multiplication, meaning
+/// that the inputs may take arbitrary `u64` values.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` are of different lengths.
+#[must_use]
+pub fn dot_product(a: &[u64], b: &[u64]) -> M61 {
+    assert_eq!(a.len(), b.len(), "slices must be of equal length");
+
+    // After the partial reduction, every factor is below `2^61 + 7`, so every
+    // product is slightly above `2^122` at most. The 128-bit sum could hold
+    // 63 of them without overflow, which is rounded down to chunks of 32
+    // products before a reduction of the sum is necessary.
+    let mut result = M61(0);
+
+    for (a, b) in a.chunks(32).zip(b.chunks(32)) {
+        let mut acc = 0u128;
+        for (&x, &y) in a.iter().zip(b) {
+            let x = (x & MODULUS) + (x >> 61);
+            let y = (y & MODULUS) + (y >> 61);
+            acc += x as u128 * y as u128;
+        }
+        result += M61::from(acc);
+    }
+
+    result
+}
+
+/// Evaluates the polynomial `c_0 + c_1 x + c_2 x^2 + ...` at the point `x`
+/// using [Horner's method](https://en.wikipedia.org/wiki/Horner%27s_method).
+///
+/// The coefficients are stored in `coeffs` with the constant term first,
+/// meaning that `coeffs[i]` is the coefficient of `x^i`.
+/// An empty slice evaluates to zero.
+#[must_use]
+pub fn eval_poly(coeffs: &[M61], x: M61) -> M61 {
+    coeffs.iter().rev().fold(M61(0), |acc, &c| acc * x + c)
+}
+
+/// Calculates the sum of all elements of `iter` modulo `2^61 - 1`.
+///
+/// The elements are added up using 128-bit integers, meaning
+/// that only a single reduction is necessary at the end.
+#[must_use]
+pub fn sum_u64<I: IntoIterator<Item = u64>>(iter: I) -> M61 {
+    // Overflowing the accumulator would require more than 2^64 elements.
+    M61::from(iter.into_iter().fold(0u128, |acc, x| acc + x as u128))
+}
+
+/// Calculates the product of all elements of `iter` modulo `2^61 - 1`.
+/// The product of an empty iterator is one.
+#[must_use]
+pub fn product_u64<I: IntoIterator<Item = u64>>(iter: I) -> M61 {
+    iter.into_iter().map(M61::from).product()
+}
+
+/// Combines two reduced numbers into the reduction of their concatenation,
+/// calculating `left * 2^right_len_bits + right` modulo `2^61 - 1`.
+///
+/// `right_len_bits` is the total number of bits of the input `right`
+/// was reduced from, e.g. `64 * b.len()` for a `b: &[u64]`, including
+/// any leading zeros. The number represented by `left` is placed above
+/// the one represented by `right`. Since the reduction functions treat
+/// slices as little-endian numbers, this means that for slices `a` and `b`
+/// of the same element type, `[b, a].concat().reduce_m61()` equals
+/// `combine(a.reduce_m61(), b.reduce_m61(), b.len() * BITS)`.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let a = [1u64, 2, 3];
+/// let b = [4u64, 5];
+///
+/// let combined = combine(a.reduce_m61(), b.reduce_m61(), 64 * b.len());
+/// assert_eq!(combined, [&b[..], &a[..]].concat().reduce_m61());
+/// ```
+///
+/// Since `2^61` is congruent to one, only `right_len_bits % 61` is
+/// relevant, which means that arbitrary lengths are supported.
+#[must_use]
+pub fn combine(left: M61, right: M61, right_len_bits: usize) -> M61 {
+    left * POW2_MOD[right_len_bits % 61] + right
+}
+
+/// Returns whether the number represented by the little-endian
+/// limbs `s` is divisible by `2^61 - 1`, e.g. to verify that a
+/// subtraction of two equal numbers resulted in zero.
+///
+/// This is a convenience wrapper around `s.reduce_m61() == M61::ZERO`.
+/// Stopping early is not sound: regardless of the limbs processed so far,
+/// the remaining limbs can always be chosen such that the total becomes
+/// divisible, which means that every limb has to be inspected.
+#[must_use]
+pub fn is_zero_reduction(s: &[u64]) -> bool {
+    use crate::M61Reduction;
+
+    s.reduce_m61() == M61::ZERO
+}
+
+/// Reduces the concatenation of `segments` without copying them
+/// into a single buffer.
+///
+/// Every segment is a little-endian number, with the segments themselves
+/// stored in little-endian ordering, i.e. the most significant segment last.
+/// The result is thus the same as `segments.concat().reduce_m61()`.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let (a, b, c) = ([1u64, 2], [3u64], [4u64, 5, 6]);
+/// let x = reduce_concat_u64(&[&a, &b, &c]);
+///
+/// assert_eq!(x, [1u64, 2, 3, 4, 5, 6].reduce_m61());
+/// ```
+#[must_use]
+pub fn reduce_concat_u64(segments: &[&[u64]]) -> M61 {
+    use crate::M61Reduction;
+
+    segments.iter().rev().fold(M61(0), |acc, segment| {
+        combine(acc, segment.reduce_m61(), shift_exponent(segment.len(), 64))
+    })
+}
+
+/// Reduces `s` like [`reduce_m61`](crate::M61Reduction::reduce_m61),
+/// additionally returning the lowest bit of the number it represents.
+///
+/// The parity is independent of the residue, which allows catching
+/// results that only coincidentally share the residue of the expected
+/// value. For a stronger secondary check, see [`reduce_dual`].
+#[must_use]
+pub fn reduce_with_parity(s: &[u64]) -> (M61, bool) {
+    use crate::M61Reduction;
+
+    let parity = s.first().is_some_and(|&x| x & 1 != 0);
+    (s.reduce_m61(), parity)
+}
+
+/// Reduces `s` modulo both `2^61 - 1` and `2^31 - 1`, loading every
+/// element only once.
+///
+/// The result is the same as that of
+/// [`reduce_m61`](crate::M61Reduction::reduce_m61) and
+/// [`reduce_m31`](crate::M31Reduction::reduce_m31). Since the moduli are
+/// coprime, a wrong result passes both checks only if it differs from the
+/// expected one by a multiple of their product, which is close to `2^92`.
+#[must_use]
+pub fn reduce_dual(s: &[u64]) -> (M61, M31) {
+    let mut hi61 = 0;
+    let mut hi31 = 0;
+
+    for lo in s.iter().copied().rev() {
+        hi61 = (lo & MODULUS) + (lo >> 61) + ((hi61 & (MODULUS >> 3)) << 3) + (hi61 >> 58);
+        hi31 = m31::horner_step(hi31, lo);
+    }
+
+    (final_reduction(hi61), m31::final_reduction(hi31))
+}
+
+/// Reduces every `stride`-th element of `s`, starting at index `offset`,
+/// as if they formed a contiguous little-endian number.
+///
+/// The `i`-th limb of the number is `s[offset + i * stride]`, where
+/// `i` runs over all values for which this index is in bounds.
+/// The result is thus the same as gathering these elements into a
+/// temporary buffer and calling [`reduce_m61`](crate::M61Reduction::reduce_m61)
+/// on it. If `offset` is not smaller than `s.len()`, the number is empty
+/// and the result is zero.
+///
+/// # Panics
+///
+/// Panics if `stride` is zero.
+#[must_use]
+pub fn reduce_u64_strided(s: &[u64], stride: usize, offset: usize) -> M61 {
+    assert!(stride != 0, "stride must be nonzero");
+
+    let s = s.get(offset..).unwrap_or(&[]);
+    let mut hi = 0;
+
+    for lo in s.iter().copied().step_by(stride).rev() {
+        hi = (lo & MODULUS) + (lo >> 61) + ((hi & (MODULUS >> 3)) << 3) + (hi >> 58);
+    }
+
+    final_reduction(hi)
+}
+
+/// Digit types supported by [`reduce_batch_into`] and [`reduce_m61_scalar`].
+mod digit {
+    use crate::definition::M61;
+
+    /// A digit type whose reduction function can be resolved ahead of time.
+    pub trait Digit: Sized {
+        /// Returns the implementation used by `reduce_m61`.
+        fn resolve() -> unsafe fn(&[Self]) -> M61;
+
+        /// Returns the portable scalar implementation.
+        fn scalar() -> fn(&[Self]) -> M61;
+    }
+
+    /// Helper macro for implementing [`Digit`] using the resolve
+    /// functions of the chosen implementation and the fallback.
+    macro_rules! make_digit_impl {
+        ($($type:ty => $resolve:ident, $func:ident;)*) => {
+            $(
+                impl Digit for $type {
+                    #[inline(always)]
+                    fn resolve() -> unsafe fn(&[Self]) -> M61 {
+                        crate::implementation::$resolve()
+                    }
+
+                    #[inline(always)]
+                    fn scalar() -> fn(&[Self]) -> M61 {
+                        crate::fallback::$func
+                    }
+                }
+            )*
+        };
+    }
+
+    make_digit_impl! {
+        u8 => resolve_u8, reduce_u8;
+        u16 => resolve_u16, reduce_u16;
+        u32 => resolve_u32, reduce_u32;
+        u64 => resolve_u64, reduce_u64;
+    }
+}
+
+/// Calculates `s mod (2^61 - 1)` like
+/// [`reduce_m61`](crate::M61Reduction::reduce_m61), but always
+/// using the portable scalar implementation.
+///
+/// The result doesn't depend on the available target features, which makes
+/// this useful for bisecting whether a mismatch between a bignum and the
+/// reduction is caused by the vectorized implementations.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let v = [1u64, 2, 3];
+/// assert_eq!(reduce_m61_scalar(&v), v.reduce_m61());
+/// ```
+#[must_use]
+pub fn reduce_m61_scalar<T: digit::Digit>(s: &[T]) -> M61 {
+    T::scalar()(s)
+}
+
+/// Reduces every slice of `slices` like
+/// [`reduce_m61`](crate::M61Reduction::reduce_m61), storing the results in `out`.
+///
+/// The implementation is selected once for the whole batch, instead of
+/// going through the dispatch for every slice. Since no allocation is
+/// required, this is also usable in `no-std` environments, e.g. with an
+/// array on the stack holding the results.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let (a, b, c) = ([1u32, 2], [3u32], [4u32, 5, 6]);
+/// let mut out = [M61::ZERO; 3];
+/// reduce_batch_into(&[&a, &b, &c], &mut out);
+///
+/// assert_eq!(out, [a.reduce_m61(), b.reduce_m61(), c.reduce_m61()]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `slices` and `out` are of different lengths.
+pub fn reduce_batch_into<T: digit::Digit>(slices: &[&[T]], out: &mut [M61]) {
+    assert_eq!(slices.len(), out.len(), "slices must be of equal length");
+
+    let reduce = T::resolve();
+    for (s, y) in slices.iter().zip(out.iter_mut()) {
+        // SAFETY: The resolved implementation is supported by the CPU.
+        *y = unsafe { reduce(s) };
+    }
+}
+
+/// Calculates the multiplicative inverses of all elements of `elems`,
+/// storing them in `out`. Zero has no inverse, and is mapped to zero.
+///
+/// This uses Montgomery's trick: the prefix products of the elements are
+/// calculated and stored in `out`, which thus also serves as scratch space.
+/// Inverting the product of all elements allows recovering the individual
+/// inverses by multiplying with the prefix products in reverse order.
+/// The result is obtained using a single inversion and fewer than `3 n`
+/// multiplications, compared to `n` inversions when dividing one by
+/// every element.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let elems = [M61::from(2u32), M61::ZERO, M61::from(5u32)];
+/// let mut out = [M61::ZERO; 3];
+/// batch_inverse_into(&elems, &mut out);
+///
+/// assert_eq!(out, [M61::ONE / elems[0], M61::ZERO, M61::ONE / elems[2]]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `elems` and `out` are of different lengths.
+pub fn batch_inverse_into(elems: &[M61], out: &mut [M61]) {
+    assert_eq!(elems.len(), out.len(), "slices must be of equal length");
+
+    // Zeros are skipped, which keeps the product invertible.
+    let mut product = M61::ONE;
+    for (&x, prefix) in elems.iter().zip(out.iter_mut()) {
+        *prefix = product;
+        if x != M61::ZERO {
+            product *= x;
+        }
+    }
+
+    // The inverse of the product of all elements up to the current one.
+    let mut inverse = M61::ONE / product;
+    for (&x, y) in elems.iter().zip(out.iter_mut()).rev() {
+        if x == M61::ZERO {
+            *y = M61::ZERO;
+        } else {
+            *y *= inverse;
+            inverse *= x;
+        }
+    }
+}
+
+/// Calculates the multiplicative inverses of all elements of `elems`,
+/// mapping zero to zero. See [`batch_inverse_into`] for details,
+/// which doesn't require an allocation.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let elems: Vec<M61> = (1..100u32).map(M61::from).collect();
+/// let inverses = batch_inverse(&elems);
+///
+/// assert!(elems.iter().zip(&inverses).all(|(&x, &y)| x * y == M61::ONE));
+/// ```
+#[cfg(feature = "std")]
+#[must_use]
+pub fn batch_inverse(elems: &[M61]) -> Vec<M61> {
+    let mut out = vec![M61::ZERO; elems.len()];
+    batch_inverse_into(elems, &mut out);
+    out
+}
+
+/// Reduces the raw bytes of `data` as a little-endian number, i.e.
+/// with the first byte being the least significant digit.
+///
+/// This accepts anything that can be viewed as bytes, like `&str`,
+/// `String`, `Vec<u8>` or `[u8; N]`, and is the same as calling
+/// [`reduce_m61`](crate::M61Reduction::reduce_m61) on `data.as_ref()`.
+/// Strings are reduced using their UTF-8 encoding.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// assert_eq!(reduce_bytes("ab"), M61::from(0x6261u32));
+/// assert_eq!(reduce_bytes(String::from("ab")), reduce_bytes([b'a', b'b']));
+/// assert_eq!(reduce_bytes(vec![1, 2]), [1u8, 2].reduce_m61());
+/// ```
+#[must_use]
+pub fn reduce_bytes(data: impl AsRef<[u8]>) -> M61 {
+    use crate::M61Reduction;
+
+    data.as_ref().reduce_m61()
+}
+
+/// Reduces the bytes `s[start..end]` as a little-endian number,
+/// e.g. to skip a header preceding the digits of a serialized number.
+///
+/// The result is the same as `s[start..end].reduce_m61()`.
+/// An empty range results in zero.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let data = [0xff, 0xff, 1, 2, 3];
+/// assert_eq!(reduce_u8_range(&data, 2, 5), [1u8, 2, 3].reduce_m61());
+/// assert_eq!(reduce_u8_range(&data, 3, 3), M61::ZERO);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `start > end` or `end > s.len()`.
+#[must_use]
+#[track_caller]
+pub fn reduce_u8_range(s: &[u8], start: usize, end: usize) -> M61 {
+    use crate::M61Reduction;
+
+    assert!(
+        start <= end,
+        "range start {start} is greater than range end {end}"
+    );
+    assert!(
+        end <= s.len(),
+        "range end {end} is out of bounds for a slice of length {}",
+        s.len()
+    );
+
+    s[start..end].reduce_m61()
+}
+
+/// The error returned by [`try_reduce_u8`] if the input is too long.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LengthError {
+    len: usize,
+    max: usize,
+}
+
+impl LengthError {
+    /// The length of the rejected input in bytes.
+    #[must_use]
+    pub const fn input_len(&self) -> usize {
+        self.len
+    }
+
+    /// The maximum length in bytes which would have been accepted.
+    #[must_use]
+    pub const fn max_len(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "input of {} bytes exceeds the maximum of {} bytes",
+            self.len, self.max
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthError {}
+
+/// Reduces the bytes of `s` as a little-endian number, like
+/// [`reduce_m61`](crate::M61Reduction::reduce_m61), unless `s` is
+/// longer than `max_bytes`.
+///
+/// This is meant as a guardrail when the length of the input stems
+/// from untrusted data, e.g. a length prefix, which could otherwise
+/// cause the reduction of a huge amount of memory.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let data = [1u8, 2, 3];
+/// assert_eq!(try_reduce_u8(&data, 3), Ok(data.reduce_m61()));
+///
+/// let err = try_reduce_u8(&data, 2).unwrap_err();
+/// assert_eq!((err.input_len(), err.max_len()), (3, 2));
+/// ```
+///
+/// # Errors
+///
+/// Returns a [`LengthError`] if `s.len() > max_bytes`.
+pub fn try_reduce_u8(s: &[u8], max_bytes: usize) -> Result<M61, LengthError> {
+    use crate::M61Reduction;
+
+    if s.len() > max_bytes {
+        return Err(LengthError {
+            len: s.len(),
+            max: max_bytes,
+        });
+    }
+
+    Ok(s.reduce_m61())
+}
+
+/// The error returned by [`reduce_f64_limbs`] if a limb
+/// isn't a non-negative integer in the supported range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidLimbError {
+    index: usize,
+}
+
+impl InvalidLimbError {
+    /// The index of the first invalid limb.
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl fmt::Display for InvalidLimbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "limb at index {} is not an integer in the range of the limb size",
+            self.index
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidLimbError {}
+
+/// Calculates `x mod (2^61 - 1)`, where `x` is the number whose
+/// digits base `2^limb_bits` are stored as `f64` values in `s`,
+/// in little-endian ordering.
+///
+/// This is meant for verifying bignum implementations storing
+/// their limbs in floating-point numbers. Every limb has to be an
+/// integer in the range `0..2^limb_bits`, with `-0.0` being treated as zero.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// // 3 + 5 * 2^51
+/// let limbs = [3.0, 5.0];
+/// assert_eq!(reduce_f64_limbs(&limbs, 51), Ok(M61::from(3u64 + (5u64 << 51))));
+///
+/// let err = reduce_f64_limbs(&[1.0, 0.5], 51).unwrap_err();
+/// assert_eq!(err.index(), 1);
+/// ```
+///
+/// # Errors
+///
+/// Returns an [`InvalidLimbError`] for the first limb which is negative,
+/// not an integer, not finite, or not smaller than `2^limb_bits`.
+///
+/// # Panics
+///
+/// Panics if `limb_bits` is not in the range `1..=53`,
+/// since `f64` can't represent all larger integers.
+#[track_caller]
+pub fn reduce_f64_limbs(s: &[f64], limb_bits: u32) -> Result<M61, InvalidLimbError> {
+    assert!(
+        (1..=53).contains(&limb_bits),
+        "limb size must be in the range 1..=53, got {limb_bits}"
+    );
+
+    let bound = (1u64 << limb_bits) as f64;
+    let mut result = M61(0);
+    let mut shift = 0;
+
+    for (index, &x) in s.iter().enumerate() {
+        // Comparisons with NaN are false, and the conversion
+        // of integers below `2^53` to `u64` and back is exact.
+        if !(x >= 0.0 && x < bound && (x as u64) as f64 == x) {
+            return Err(InvalidLimbError { index });
+        }
+
+        result += M61(x as u64) * POW2_MOD[shift];
+        shift = (shift + limb_bits as usize) % 61;
+    }
+
+    Ok(result)
+}
+
+/// The maximum number of limbs in a slice returned by [`generate_test_vectors`].
+#[cfg(feature = "std")]
+const TEST_VECTOR_MAX_LEN: u64 = 64;
+
+/// Deterministically generates `count` pairs of random limb
+/// slices and their reductions modulo `2^61 - 1`.
+///
+/// The same `seed` always leads to the same vectors, which allows
+/// exporting them to validate implementations in other languages.
+/// The slices have lengths of up to 64 limbs, including empty ones.
+/// Every limb is either uniformly random, or one of the values `0`,
+/// `2^61 - 1` and `2^64 - 1`, which commonly expose carry bugs.
+///
+/// The limbs are generated using [SplitMix64](https://prng.di.unimi.it/splitmix64.c),
+/// seeded with `seed`. Each slice draws one value for its length,
+/// followed by one value per limb: values with the lowest two bits equal
+/// to zero are replaced by one of the special values above, which one
+/// being determined by the next two bits modulo three.
+#[cfg(feature = "std")]
+#[must_use]
+pub fn generate_test_vectors(seed: u64, count: usize) -> Vec<(Vec<u64>, M61)> {
+    use crate::M61Reduction;
+
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+
+    (0..count)
+        .map(|_| {
+            let len = next() % (TEST_VECTOR_MAX_LEN + 1);
+            let limbs: Vec<u64> = (0..len)
+                .map(|_| match next() {
+                    x if x & 3 != 0 => x,
+                    x => [0, MODULUS, u64::MAX][(x >> 2) as usize % 3],
+                })
+                .collect();
+            let reduced = limbs.reduce_m61();
+            (limbs, reduced)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    quickcheck::quickcheck! {
+        fn dot_product_correct(v: Vec<(u64, u64)>) -> bool {
+            let (a, b): (Vec<u64>, Vec<u64>) = v.into_iter().unzip();
+
+            let expected = a
+                .iter()
+                .zip(&b)
+                .map(|(&x, &y)| M61::from(x) * M61::from(y))
+                .sum::<M61>();
+            let actual = dot_product(&a, &b);
+
+            expected == actual
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn eval_poly_correct(coeffs: Vec<u64>, x: u64) -> bool {
+            let coeffs: Vec<M61> = coeffs.into_iter().map(M61::from).collect();
+            let x = M61::from(x);
+
+            let mut power = M61(1);
+            let mut expected = M61(0);
+            for &c in &coeffs {
+                expected += c * power;
+                power *= x;
+            }
+
+            expected == eval_poly(&coeffs, x)
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn sum_u64_correct(v: Vec<u64>) -> bool {
+            sum_u64(v.iter().copied()) == v.iter().map(|&x| M61::from(x)).sum::<M61>()
+        }
+
+        fn product_u64_correct(v: Vec<u64>) -> bool {
+            product_u64(v.iter().copied()) == v.iter().map(|&x| M61::from(x)).product::<M61>()
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_bytes_correct(s: String) -> bool {
+            use crate::M61Reduction;
+
+            reduce_bytes(&s) == s.as_bytes().reduce_m61()
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_u8_range_correct(v: Vec<u8>, a: usize, b: usize) -> bool {
+            use crate::M61Reduction;
+
+            let (start, end) = match v.len() {
+                0 => (0, 0),
+                len => {
+                    let (a, b) = (a % (len + 1), b % (len + 1));
+                    (a.min(b), a.max(b))
+                }
+            };
+            reduce_u8_range(&v, start, end) == v[start..end].reduce_m61()
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn reduce_u8_range_out_of_bounds() {
+        let _ = reduce_u8_range(&[1, 2, 3], 1, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than range end")]
+    fn reduce_u8_range_inverted() {
+        let _ = reduce_u8_range(&[1, 2, 3], 2, 1);
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_f64_limbs_correct(v: Vec<u64>, limb_bits: u32) -> bool {
+            let limb_bits = limb_bits % 53 + 1;
+            let limbs: Vec<u64> = v.iter().map(|x| x >> (64 - limb_bits)).collect();
+            let floats: Vec<f64> = limbs.iter().map(|&x| x as f64).collect();
+
+            // Horner's method with the point of evaluation `2^limb_bits`.
+            let mut expected = M61(0);
+            for &limb in limbs.iter().rev() {
+                expected = expected * POW2_MOD[limb_bits as usize] + M61(limb);
+            }
+
+            reduce_f64_limbs(&floats, limb_bits) == Ok(expected)
+        }
+    }
+
+    #[test]
+    fn reduce_f64_limbs_invalid() {
+        let valid = [1.0, -0.0, 2.0f64.powi(51) - 1.0];
+        assert_eq!(
+            reduce_f64_limbs(&valid, 51),
+            Ok(M61(1) + M61((1 << 51) - 1) * POW2_MOD[41])
+        );
+
+        for invalid in [-1.0, 0.5, 2.0f64.powi(51), f64::NAN, f64::INFINITY] {
+            let limbs = [1.0, 2.0, invalid, 3.0];
+            assert_eq!(
+                reduce_f64_limbs(&limbs, 51).map_err(|e| e.index()),
+                Err(2),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "limb size must be in the range 1..=53")]
+    fn reduce_f64_limbs_invalid_size() {
+        let _ = reduce_f64_limbs(&[], 54);
+    }
+
+    #[test]
+    fn try_reduce_u8_limits() {
+        use crate::M61Reduction;
+
+        let data: Vec<u8> = (0..100).collect();
+        assert_eq!(try_reduce_u8(&data, 100), Ok(data.reduce_m61()));
+        assert_eq!(try_reduce_u8(&data, usize::MAX), Ok(data.reduce_m61()));
+        assert_eq!(try_reduce_u8(&[], 0), Ok(M61::ZERO));
+
+        let err = try_reduce_u8(&data, 99).unwrap_err();
+        assert_eq!((err.input_len(), err.max_len()), (100, 99));
+        assert_eq!(
+            err.to_string(),
+            "input of 100 bytes exceeds the maximum of 99 bytes"
+        );
+    }
+
+    quickcheck::quickcheck! {
+        fn batch_inverse_correct(v: Vec<u64>) -> bool {
+            // Every fourth element is zero to exercise the special case.
+            let elems: Vec<M61> = v
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| if i % 4 == 3 { M61::ZERO } else { M61::from(x) })
+                .collect();
+
+            batch_inverse(&elems).iter().zip(&elems).all(|(&y, &x)| {
+                if x == M61::ZERO {
+                    y == M61::ZERO
+                } else {
+                    y == M61::ONE / x
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn batch_inverse_all_zero() {
+        assert_eq!(batch_inverse(&[M61::ZERO; 3]), [M61::ZERO; 3]);
+        assert!(batch_inverse(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "equal length")]
+    fn batch_inverse_into_length_mismatch() {
+        batch_inverse_into(&[M61::ONE; 2], &mut [M61::ZERO; 3]);
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_batch_into_correct(v: Vec<Vec<u8>>, w: Vec<Vec<u64>>) -> bool {
+            use crate::M61Reduction;
+
+            let slices: Vec<&[u8]> = v.iter().map(|s| &s[..]).collect();
+            let mut out = vec![M61::ZERO; v.len()];
+            reduce_batch_into(&slices, &mut out);
+            let bytes_correct = out.iter().zip(&v).all(|(&y, s)| y == s.reduce_m61());
+
+            let slices: Vec<&[u64]> = w.iter().map(|s| &s[..]).collect();
+            let mut out = vec![M61::ZERO; w.len()];
+            reduce_batch_into(&slices, &mut out);
+            let words_correct = out.iter().zip(&w).all(|(&y, s)| y == s.reduce_m61());
+
+            bytes_correct && words_correct
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_m61_scalar_correct(v: Vec<u8>, w: Vec<u64>) -> bool {
+            use crate::M61Reduction;
+
+            reduce_m61_scalar(&v) == v.reduce_m61() && reduce_m61_scalar(&w) == w.reduce_m61()
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "equal length")]
+    fn reduce_batch_into_length_mismatch() {
+        reduce_batch_into::<u16>(&[&[1], &[2]], &mut [M61::ZERO; 1]);
+    }
+
+    #[test]
+    fn sum_u64_max() {
+        for len in 0..100 {
+            assert_eq!(
+                sum_u64(vec![u64::MAX; len]),
+                M61::from(len) * M61::from(u64::MAX)
+            );
+        }
+    }
+
+    #[test]
+    fn dot_product_max() {
+        for len in 0..200 {
+            let v = vec![u64::MAX; len];
+            let expected = M61::from(len) * M61::from(u64::MAX) * M61::from(u64::MAX);
+            assert_eq!(dot_product(&v, &v), expected);
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_u64_strided_correct(v: Vec<u64>, stride: u8, offset: u8) -> bool {
+            use crate::M61Reduction;
+
+            let stride = stride as usize % 8 + 1;
+            let offset = offset as usize % 8;
+            let gathered: Vec<u64> = v.iter().copied().skip(offset).step_by(stride).collect();
+
+            reduce_u64_strided(&v, stride, offset) == gathered.reduce_m61()
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn combine_correct(a: Vec<u8>, b: Vec<u8>) -> bool {
+            use crate::M61Reduction;
+
+            let concat: Vec<u8> = b.iter().chain(&a).copied().collect();
+            combine(a.reduce_m61(), b.reduce_m61(), 8 * b.len()) == concat.reduce_m61()
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_concat_correct(segments: Vec<Vec<u64>>) -> bool {
+            use crate::M61Reduction;
+
+            let slices: Vec<&[u64]> = segments.iter().map(|s| &s[..]).collect();
+            reduce_concat_u64(&slices) == segments.concat().reduce_m61()
+        }
+    }
+
+    #[test]
+    fn is_zero_reduction_correct() {
+        assert!(is_zero_reduction(&[]));
+        assert!(is_zero_reduction(&[0; 100]));
+        assert!(is_zero_reduction(&[MODULUS]));
+        // `2^64 - 8 = 8 * (2^61 - 1)`
+        assert!(is_zero_reduction(&[u64::MAX - 7]));
+        assert!(is_zero_reduction(&[0, MODULUS, u64::MAX - 7]));
+        assert!(!is_zero_reduction(&[0, 0, 1]));
+        assert!(!is_zero_reduction(&[1]));
+    }
+
+    #[test]
+    fn reduce_concat_empty() {
+        assert_eq!(reduce_concat_u64(&[]), M61(0));
+        assert_eq!(reduce_concat_u64(&[&[], &[1], &[]]), M61(1));
+    }
+
+    #[test]
+    fn combine_powers_of_two() {
+        let mut expected = M61(1);
+        for bits in 0..200 {
+            assert_eq!(combine(M61(1), M61(0), bits), expected);
+            expected += expected;
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_with_parity_correct(v: Vec<u64>) -> bool {
+            use crate::M61Reduction;
+
+            let (residue, parity) = reduce_with_parity(&v);
+            residue == v.reduce_m61() && parity == (v.first().copied().unwrap_or(0) % 2 == 1)
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_dual_correct(v: Vec<u64>) -> bool {
+            use crate::{M31Reduction, M61Reduction};
+
+            reduce_dual(&v) == (v.reduce_m61(), v.reduce_m31())
+        }
+    }
+
+    #[test]
+    fn reduce_dual_max() {
+        use crate::{M31Reduction, M61Reduction};
+
+        for len in 0..100 {
+            let v = vec![u64::MAX; len];
+            assert_eq!(reduce_dual(&v), (v.reduce_m61(), v.reduce_m31()));
+        }
+    }
+
+    #[test]
+    fn test_vectors_deterministic() {
+        use crate::M61Reduction;
+
+        let vectors = generate_test_vectors(42, 100);
+        assert_eq!(vectors.len(), 100);
+        assert_eq!(vectors, generate_test_vectors(42, 100));
+        assert_ne!(vectors, generate_test_vectors(43, 100));
+        assert_eq!(generate_test_vectors(42, 10), vectors[..10]);
+
+        for (limbs, reduced) in &vectors {
+            assert!(limbs.len() as u64 <= TEST_VECTOR_MAX_LEN);
+            assert_eq!(
+                *reduced,
+                crate::reference::reference_reduce(&limbs_to_bytes(limbs))
+            );
+            assert_eq!(*reduced, limbs.reduce_m61_scalar());
+        }
+    }
+
+    fn limbs_to_bytes(limbs: &[u64]) -> Vec<u8> {
+        limbs.iter().flat_map(|x| x.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn reduce_u64_strided_out_of_bounds() {
+        assert_eq!(reduce_u64_strided(&[1, 2, 3], 1, 3), M61(0));
+        assert_eq!(reduce_u64_strided(&[1, 2, 3], 2, 10), M61(0));
+        assert_eq!(reduce_u64_strided(&[], 1, 0), M61(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn reduce_u64_strided_zero_stride() {
+        let _ = reduce_u64_strided(&[1, 2, 3], 0, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dot_product_length_mismatch() {
+        let _ = dot_product(&[1, 2], &[3]);
+    }
+}
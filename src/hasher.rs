@@ -0,0 +1,104 @@
+//! A [`Hasher`] based on the reduction modulo `2^61 - 1`.
+
+use core::hash::Hasher;
+
+use crate::accumulator::M61Accumulator;
+
+/// A [`Hasher`] which interprets all written bytes as a single
+/// little-endian number and reduces it modulo `2^61 - 1`.
+///
+/// Since the modulus is prime, the resulting hashes distribute well
+/// for typical keys. Combined with [`BuildHasherDefault`](core::hash::BuildHasherDefault),
+/// the hasher can be used with hash-based collections:
+///
+/// ```
+/// use m61_modulus::*;
+/// use std::collections::HashMap;
+/// use std::hash::BuildHasherDefault;
+///
+/// let mut map: HashMap<&str, u32, BuildHasherDefault<M61Hasher>> = HashMap::default();
+/// map.insert("answer", 42);
+///
+/// assert_eq!(map["answer"], 42);
+/// ```
+///
+/// This hasher is **not** cryptographically secure, nor resistant
+/// against collisions crafted on purpose. For example, appending zero bytes
+/// doesn't change the hash, and the hash is a linear function of the input.
+/// It must not be used for keys controlled by an adversary.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct M61Hasher {
+    acc: M61Accumulator,
+}
+
+impl M61Hasher {
+    /// Creates a hasher for which no bytes have been written yet.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            acc: M61Accumulator::new(),
+        }
+    }
+}
+
+impl Hasher for M61Hasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.acc.push_u8(bytes);
+    }
+
+    /// Returns the canonical representative of the reduction.
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.acc.get().get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::M61Reduction;
+    use core::hash::{BuildHasherDefault, Hash};
+    use std::collections::HashSet;
+
+    quickcheck::quickcheck! {
+        fn hasher_equals_reduction(chunks: Vec<Vec<u8>>) -> bool {
+            let mut hasher = M61Hasher::new();
+            for chunk in &chunks {
+                hasher.write(chunk);
+            }
+
+            hasher.finish() == chunks.concat().reduce_m61().get()
+        }
+
+        fn hasher_integers(x: u64, y: u16) -> bool {
+            let mut hasher = M61Hasher::new();
+            hasher.write_u64(x);
+            hasher.write_u16(y);
+
+            let mut bytes = x.to_ne_bytes().to_vec();
+            bytes.extend_from_slice(&y.to_ne_bytes());
+            hasher.finish() == bytes.reduce_m61().get()
+        }
+    }
+
+    #[test]
+    fn hasher_in_hash_set() {
+        let mut set: HashSet<String, BuildHasherDefault<M61Hasher>> = HashSet::default();
+        for i in 0..1000 {
+            set.insert(i.to_string());
+        }
+
+        assert_eq!(set.len(), 1000);
+        assert!((0..1000).all(|i| set.contains(&i.to_string())));
+
+        // Strings are terminated when hashed, so trailing zeros make a difference.
+        let hash = |s: &str| {
+            let mut hasher = M61Hasher::new();
+            s.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_ne!(hash("a"), hash("a\0"));
+    }
+}
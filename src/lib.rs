@@ -2,7 +2,7 @@
 //! Aimed at testing bignum implementations.
 //!
 //! ## Usage
-//! 
+//!
 //! The crate comes with a trait [`M61Reduction`] and a type [`M61`].
 //! `M61` is an integer in which all arithmetic is performed the
 //! 61st Mersenne number, `2^61 - 1`.
@@ -15,30 +15,62 @@
 //!
 //! assert_eq!(x, y);
 //! ```
-//! 
+//!
 //! The trait `M61Reduction` is implemented for unsigned integer slices,
+//! for slices of `i128` in two's complement, and for slices of the
+//! smaller signed integers, whose digits are signed individually,
 //! providing two functions for reducing the modulo `2^61 - 1`,
 //! as if they were digits in a bignum implementation.
-//! 
+//!
 //! ```
 //! use m61_modulus::*;
 //!
 //! let x = [1u16, 734u16, 24u16].reduce_m61();
 //! let y = M61::from(1) + M61::from(734 << 16) + M61::from(24u64 << 32);
-//! 
+//!
 //! assert_eq!(x, y);
 //! ```
 //!
 //! The functions are `reduce_m61`, which is single-threaded, and `reduce_m61_parallelized`,
 //! which may spawn additional threads.
 //!
-//! This crate comes with two features:
+//! For an additional, independent check, the type [`M31`] and the trait
+//! [`M31Reduction`] provide the same for the 31st Mersenne number, `2^31 - 1`.
+//!
+//! This crate comes with six features:
 //! * `nightly`, which enables support for additional nightly-only ISA extensions
-//!   like AVX512. Disabled by default.
+//!   like NEON on 32-bit arm, or AVX512 on compilers older than Rust 1.89,
+//!   which is used without this feature on newer ones. Disabled by default.
+//! * `num-traits`, which implements `Zero`, `One` and `Inv` from the
+//!   `num-traits` crate for [`M61`], allowing it to be used in generic code.
+//!   Disabled by default.
+//! * `rand`, which allows sampling random values of [`M61`] using the `rand` crate.
+//!   Disabled by default.
+//! * `rayon`, which provides `par_sum` and `par_product` for reducing
+//!   large slices of [`M61`] using the `rayon` thread pool, as well as
+//!   `M61Reduction::reduce_m61_rayon`. Implies `std`.
+//!   Disabled by default.
+//! * `serde`, which implements `Serialize` and `Deserialize` for [`M61`].
+//!   Human-readable formats store values as decimal strings, since they
+//!   may exceed the integers which JSON numbers can represent precisely.
+//!   Disabled by default.
 //! * `std`, which provides access to the `reduce_m61_parallelized` function,
 //!   which requires the Rust standard library. If disabled, this crate will
 //!   also work on `no-std` targets. Enabled by default.
 //!
+//! Additionally, the following features can be enabled to pin the
+//! implementation used by [`M61Reduction::reduce_m61`] at compile time,
+//! bypassing the selection at runtime. This is useful for reproducible
+//! benchmarks, or for reducing the code size if the deployment target is known.
+//! If several of them are enabled, e.g. by different crates in the same
+//! dependency graph, the first one in this list takes precedence, and the
+//! requirements of the others don't have to be met.
+//! * `force-scalar`, which uses the scalar implementation on every target.
+//! * `force-sse2`, which uses the SSE2 implementation.
+//!   Requires an x86 target with the `sse2` target feature enabled.
+//! * `force-avx2`, which uses the AVX2 implementation. Requires an x86 target
+//!   with the `avx2` target feature enabled, e.g. using `-C target-cpu=native`.
+//!
 //! ## Background
 //!
 //! This crate is designed around verifying the results of bignum implementations
@@ -51,19 +83,82 @@
 //! * It is a prime number, which means the results distribute well given random input.
 //! * Its difference of one to the next power of two makes calcuations incredibly cheap.
 
-#![cfg_attr(feature = "nightly", feature(avx512_target_feature, stdsimd))]
+#![cfg_attr(avx512_nightly, feature(avx512_target_feature))]
+#![cfg_attr(
+    all(feature = "nightly", target_arch = "arm"),
+    feature(arm_target_feature, stdarch_arm_neon_intrinsics)
+)]
+#![cfg_attr(
+    all(
+        feature = "nightly",
+        feature = "std",
+        target_arch = "arm",
+        not(target_feature = "neon"),
+    ),
+    feature(stdarch_arm_feature_detection)
+)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod accumulator;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod backend;
 mod definition;
+mod fallback;
+mod fallback_swar;
+mod functions;
+mod hasher;
+mod m31;
+#[cfg(feature = "rayon")]
+mod par;
+mod parse;
+mod pow_table;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(test)]
+mod reference;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(target_has_atomic = "64")]
+mod spawn;
+
+// The `force-*` features are only checked if they take effect,
+// i.e. if no feature with higher precedence is enabled.
+
+#[cfg(all(
+    feature = "force-sse2",
+    not(feature = "force-scalar"),
+    not(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse2",
+    )),
+))]
+compile_error!("the `force-sse2` feature requires an x86 target with SSE2 enabled");
+
+#[cfg(all(
+    feature = "force-avx2",
+    not(any(feature = "force-scalar", feature = "force-sse2")),
+    not(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "avx2",
+    )),
+))]
+compile_error!("the `force-avx2` feature requires an x86 target with AVX2 enabled");
 
 cfg_if::cfg_if! {
-    if #[cfg(all(
+    if #[cfg(feature = "force-scalar")] {
+        use fallback as implementation;
+    } else if #[cfg(all(
         not(miri),
         target_endian = "little",
         any(
             all(target_arch = "x86", target_feature = "sse2"),
             target_arch = "x86_64",
-            all(feature = "nightly", target_arch = "arm", target_feature = "neon"),
+            all(
+                feature = "nightly",
+                target_arch = "arm",
+                any(target_feature = "neon", feature = "std"),
+            ),
             target_arch = "aarch64",
             all(target_family = "wasm", target_feature = "simd128"),
         ),
@@ -71,111 +166,395 @@ cfg_if::cfg_if! {
         #[path = "./simd/mod.rs"]
         mod implementation;
     } else {
-        #[path = "./fallback.rs"]
-        mod implementation;
+        // This includes riscv64, which has no dedicated backend,
+        // since `core::arch` doesn't provide intrinsics for the V extension yet.
+        use fallback_swar as implementation;
     }
 }
 
-#[cfg(all(test, not(miri)))]
-mod fallback;
-
 #[cfg(feature = "std")]
 mod parallelized;
 
-pub use crate::definition::M61;
+#[cfg(feature = "std")]
+pub use crate::accumulator::reduce_m61_reader;
+pub use crate::accumulator::M61Accumulator;
+pub use crate::definition::{M61, POW2_MOD};
+#[cfg(feature = "std")]
+pub use crate::functions::{batch_inverse, generate_test_vectors};
+pub use crate::functions::{
+    batch_inverse_into, combine, dot_product, eval_poly, is_zero_reduction, product_u64,
+    reduce_batch_into, reduce_bytes, reduce_concat_u64, reduce_dual, reduce_f64_limbs,
+    reduce_m61_scalar, reduce_u64_strided, reduce_u8_range, reduce_with_parity, sum_u64,
+    try_reduce_u8, InvalidLimbError, LengthError,
+};
+pub use crate::hasher::M61Hasher;
+pub use crate::m31::M31;
+#[cfg(feature = "rayon")]
+pub use crate::par::{par_product, par_sum};
+#[cfg(feature = "std")]
+pub use crate::parallelized::ParallelStrategy;
+pub use crate::parse::{reduce_hex, DecodeM61Error, ParseM61Error};
+pub use crate::pow_table::PowTable;
+#[cfg(target_has_atomic = "64")]
+pub use crate::spawn::{reduce_m61_with_spawner, Spawner};
+
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping};
+
+/// Eagerly selects the implementations used by [`M61Reduction::reduce_m61`].
+///
+/// On some targets, the fastest implementation is chosen at runtime
+/// based on the available target features. This happens lazily during
+/// the first call of a reduction function, which introduces a small
+/// delay. Calling this function ahead of time, e.g. at startup, moves
+/// that cost out of latency-sensitive code. On targets where the
+/// implementation is chosen at compile time, this function does nothing.
+///
+/// Calling this function is never necessary for correctness.
+/// Once it returns, subsequent reductions on the same thread use the
+/// selected implementations directly. Other threads may still perform
+/// the selection themselves, which is harmless, since every thread
+/// arrives at the same result.
+#[inline]
+pub fn force_init() {
+    implementation::force_init();
+}
+
+mod sealed {
+    use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping};
+
+    /// Prevents [`M61Reduction`](super::M61Reduction) and
+    /// [`M31Reduction`](super::M31Reduction) from being implemented
+    /// outside of this crate, so that methods can be added to them
+    /// without breaking downstream code.
+    pub trait Sealed {}
+
+    /// Helper macro for implementing [`Sealed`] for slices of the given types.
+    macro_rules! make_sealed_impl {
+        ($($type:ty),*) => {
+            $(impl Sealed for [$type] {})*
+        };
+    }
+
+    make_sealed_impl!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128);
+    make_sealed_impl!(Wrapping<u8>, Wrapping<u16>, Wrapping<u32>, Wrapping<u64>);
+    make_sealed_impl!(Wrapping<usize>, NonZeroUsize);
+    make_sealed_impl!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64);
+
+    impl<const N: usize> Sealed for [[u64; N]] {}
+}
 
 /// Helper trait for making the fuctions accessible using the dot operator.
-pub trait M61Reduction {
+///
+/// This trait is sealed, i.e. it can't be implemented outside of this crate.
+pub trait M61Reduction: sealed::Sealed {
     /// Calculates `self mod (2^61 - 1)`, assuming `self` is a number
-    /// base `2^Self::BITS`, with digits stored in little-edian ordering.
+    /// base `2^Self::BITS`, with digits stored in little-endian ordering.
     #[must_use]
     fn reduce_m61(&self) -> M61;
 
     /// Calculates `self mod (2^61 - 1)`, assuming `self` is a number
-    /// base `2^Self::BITS`, with digits stored in little-edian ordering.
+    /// base `2^Self::BITS`, with digits stored in little-endian ordering.
+    ///
+    /// This function always uses the portable scalar implementation,
+    /// regardless of the available target features. It is slower than
+    /// [`M61Reduction::reduce_m61`], but useful as a reference when
+    /// the vectorized implementations are suspected to be faulty.
+    #[must_use]
+    fn reduce_m61_scalar(&self) -> M61;
+
+    /// Calculates `self mod (2^61 - 1)`, assuming `self` is a number
+    /// base `2^Self::BITS`, with digits stored in big-endian ordering,
+    /// i.e. with the most significant digit first.
+    ///
+    /// The result is the same as reducing the slice with the order of its
+    /// digits reversed, without requiring a copy of the whole input.
+    /// This uses the portable scalar implementation.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let x = [1u16, 2, 3].reduce_m61_be();
+    /// assert_eq!(x, [3u16, 2, 1].reduce_m61());
+    /// ```
+    #[must_use]
+    fn reduce_m61_be(&self) -> M61;
+
+    /// Calculates `self mod (2^61 - 1)` like [`M61Reduction::reduce_m61`],
+    /// but with the order of the bits within every digit reversed.
+    ///
+    /// The digits themselves remain in little-endian ordering. The result
+    /// is the same as reducing the slice obtained by applying `reverse_bits`
+    /// to every digit, without requiring a copy of the whole input.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let x = [0b0000_0001u8, 0b1000_0000].reduce_m61_bitrev();
+    /// assert_eq!(x, [0b1000_0000u8, 0b0000_0001].reduce_m61());
+    /// ```
+    #[must_use]
+    fn reduce_m61_bitrev(&self) -> M61;
+
+    /// Calculates `self mod (2^61 - 1)`, assuming `self` is a number
+    /// base `2^Self::BITS`, with digits stored in little-endian ordering.
     ///
     /// This function is parallelized, using at most `max_thread_count`
     /// threads to calculate the result.
     #[cfg(feature = "std")]
     #[must_use]
     fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61;
+
+    /// Calculates `self mod (2^61 - 1)`, assuming `self` is a number
+    /// base `2^Self::BITS`, with digits stored in little-endian ordering.
+    ///
+    /// This function is parallelized, using at most `max_thread_count`
+    /// threads to calculate the result. The input is split up
+    /// between the threads as determined by `strategy`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn reduce_m61_parallelized_with(
+        &self,
+        max_thread_count: usize,
+        strategy: ParallelStrategy,
+    ) -> M61;
+
+    /// Calculates `self mod (2^61 - 1)`, assuming `self` is a number
+    /// base `2^Self::BITS`, with digits stored in little-endian ordering.
+    ///
+    /// This function is parallelized using `rayon`. Instead of spawning
+    /// threads like [`M61Reduction::reduce_m61_parallelized`], the input
+    /// is split into chunks which are reduced as tasks of the current thread
+    /// pool, i.e. the one this is called from, or the global one otherwise.
+    /// This avoids competing with the pool for cores if the caller already
+    /// uses `rayon`. Pools with a limited number of threads can be used
+    /// by calling this inside of `ThreadPool::install`.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let v: Vec<u64> = (0..100_000).collect();
+    /// assert_eq!(v.reduce_m61_rayon(), v.reduce_m61());
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    fn reduce_m61_rayon(&self) -> M61;
 }
 
-impl M61Reduction for [u8] {
-    #[inline(always)]
-    fn reduce_m61(&self) -> M61 {
-        // SAFETY: The `implementation` module only defers to unsafe
-        // versions if their safety conditions are met.
-        #[allow(unused_unsafe)]
-        unsafe {
-            implementation::reduce_u8(self)
+/// Reduces `s` with every digit replaced by `map(digit)`, e.g. with
+/// its bits reversed.
+///
+/// The digits are mapped in chunks using a buffer on the stack,
+/// which are reduced using the vectorized implementations. The results
+/// are combined like in the [`accumulator`] module.
+fn reduce_mapped<T: Copy + Default>(s: &[T], map: fn(T) -> T, bits: u32) -> M61
+where
+    [T]: M61Reduction,
+{
+    const CHUNK: usize = 256;
+
+    let mut buffer = [T::default(); CHUNK];
+    let mut result = M61(0);
+    let chunk_shift = (CHUNK % 61) as u32 * bits % 61;
+    let mut shift = 0;
+
+    for chunk in s.chunks(CHUNK) {
+        let buffer = &mut buffer[..chunk.len()];
+        for (dst, &src) in buffer.iter_mut().zip(chunk) {
+            *dst = map(src);
         }
-    }
 
-    #[cfg(feature = "std")]
-    #[inline(always)]
-    fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
-        parallelized::reduce_u8(self, max_thread_count)
+        result += buffer.reduce_m61() * M61(1 << shift);
+        shift = (shift + chunk_shift) % 61;
     }
+
+    result
+}
+
+/// Helper macro for implementing [`M61Reduction`]
+/// for the primitive unsigned integer types.
+macro_rules! make_reduction_impl {
+    ($type:ty, $func:ident) => {
+        impl M61Reduction for [$type] {
+            #[inline(always)]
+            fn reduce_m61(&self) -> M61 {
+                // SAFETY: The `implementation` module only defers to unsafe
+                // versions if their safety conditions are met.
+                #[allow(unused_unsafe)]
+                unsafe {
+                    implementation::$func(self)
+                }
+            }
+
+            #[inline(always)]
+            fn reduce_m61_scalar(&self) -> M61 {
+                fallback::$func(self)
+            }
+
+            #[inline(always)]
+            fn reduce_m61_be(&self) -> M61 {
+                fallback::be::$func(self)
+            }
+
+            #[inline]
+            fn reduce_m61_bitrev(&self) -> M61 {
+                reduce_mapped(self, <$type>::reverse_bits, <$type>::BITS)
+            }
+
+            #[cfg(feature = "std")]
+            #[inline(always)]
+            fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
+                parallelized::$func(self, max_thread_count, ParallelStrategy::Static)
+            }
+
+            #[cfg(feature = "std")]
+            #[inline(always)]
+            fn reduce_m61_parallelized_with(
+                &self,
+                max_thread_count: usize,
+                strategy: ParallelStrategy,
+            ) -> M61 {
+                parallelized::$func(self, max_thread_count, strategy)
+            }
+
+            #[cfg(feature = "rayon")]
+            #[inline(always)]
+            fn reduce_m61_rayon(&self) -> M61 {
+                par::$func(self)
+            }
+        }
+    };
 }
 
-impl M61Reduction for [u16] {
+make_reduction_impl!(u8, reduce_u8);
+make_reduction_impl!(u16, reduce_u16);
+make_reduction_impl!(u32, reduce_u32);
+make_reduction_impl!(u64, reduce_u64);
+
+impl M61Reduction for [usize] {
     #[inline(always)]
     fn reduce_m61(&self) -> M61 {
-        // SAFETY: The `implementation` module only defers to unsafe
-        // versions if their safety conditions are met.
-        #[allow(unused_unsafe)]
+        // SAFETY: Within the body, we turn the input slice into a
+        // slice of of the same length and with a identically sized type.
+        // Thus, the memory regions are the same.
         unsafe {
-            implementation::reduce_u16(self)
+            use core::slice::from_raw_parts;
+            let (ptr, len) = (self.as_ptr(), self.len());
+            match core::mem::size_of::<usize>() {
+                2 => from_raw_parts(ptr as *const u16, len).reduce_m61(),
+                4 => from_raw_parts(ptr as *const u32, len).reduce_m61(),
+                8 => from_raw_parts(ptr as *const u64, len).reduce_m61(),
+                _ => unreachable!("an address has only 16, 32 or 64 bits"),
+            }
         }
     }
 
-    #[cfg(feature = "std")]
     #[inline(always)]
-    fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
-        parallelized::reduce_u16(self, max_thread_count)
+    fn reduce_m61_scalar(&self) -> M61 {
+        // SAFETY: Within the body, we turn the input slice into a
+        // slice of of the same length and with a identically sized type.
+        // Thus, the memory regions are the same.
+        unsafe {
+            use core::slice::from_raw_parts;
+            let (ptr, len) = (self.as_ptr(), self.len());
+            match core::mem::size_of::<usize>() {
+                2 => from_raw_parts(ptr as *const u16, len).reduce_m61_scalar(),
+                4 => from_raw_parts(ptr as *const u32, len).reduce_m61_scalar(),
+                8 => from_raw_parts(ptr as *const u64, len).reduce_m61_scalar(),
+                _ => unreachable!("an address has only 16, 32 or 64 bits"),
+            }
+        }
     }
-}
 
-impl M61Reduction for [u32] {
     #[inline(always)]
-    fn reduce_m61(&self) -> M61 {
-        // SAFETY: The `implementation` module only defers to unsafe
-        // versions if their safety conditions are met.
-        #[allow(unused_unsafe)]
+    fn reduce_m61_be(&self) -> M61 {
+        // SAFETY: Within the body, we turn the input slice into a
+        // slice of of the same length and with a identically sized type.
+        // Thus, the memory regions are the same.
         unsafe {
-            implementation::reduce_u32(self)
+            use core::slice::from_raw_parts;
+            let (ptr, len) = (self.as_ptr(), self.len());
+            match core::mem::size_of::<usize>() {
+                2 => from_raw_parts(ptr as *const u16, len).reduce_m61_be(),
+                4 => from_raw_parts(ptr as *const u32, len).reduce_m61_be(),
+                8 => from_raw_parts(ptr as *const u64, len).reduce_m61_be(),
+                _ => unreachable!("an address has only 16, 32 or 64 bits"),
+            }
         }
     }
 
-    #[cfg(feature = "std")]
     #[inline(always)]
-    fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
-        parallelized::reduce_u32(self, max_thread_count)
+    fn reduce_m61_bitrev(&self) -> M61 {
+        // SAFETY: Within the body, we turn the input slice into a
+        // slice of of the same length and with a identically sized type.
+        // Thus, the memory regions are the same.
+        unsafe {
+            use core::slice::from_raw_parts;
+            let (ptr, len) = (self.as_ptr(), self.len());
+            match core::mem::size_of::<usize>() {
+                2 => from_raw_parts(ptr as *const u16, len).reduce_m61_bitrev(),
+                4 => from_raw_parts(ptr as *const u32, len).reduce_m61_bitrev(),
+                8 => from_raw_parts(ptr as *const u64, len).reduce_m61_bitrev(),
+                _ => unreachable!("an address has only 16, 32 or 64 bits"),
+            }
+        }
     }
-}
 
-impl M61Reduction for [u64] {
+    #[cfg(feature = "std")]
     #[inline(always)]
-    fn reduce_m61(&self) -> M61 {
-        // SAFETY: The `implementation` module only defers to unsafe
-        // versions if their safety conditions are met.
-        #[allow(unused_unsafe)]
+    fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
+        // SAFETY: Within the body, we turn the input slice into a
+        // slice of of the same length and with a identically sized type.
+        // Thus, the memory regions are the same.
         unsafe {
-            implementation::reduce_u64(self)
+            use core::slice::from_raw_parts;
+            let (ptr, len) = (self.as_ptr(), self.len());
+            match core::mem::size_of::<usize>() {
+                2 => {
+                    from_raw_parts(ptr as *const u16, len).reduce_m61_parallelized(max_thread_count)
+                }
+                4 => {
+                    from_raw_parts(ptr as *const u32, len).reduce_m61_parallelized(max_thread_count)
+                }
+                8 => {
+                    from_raw_parts(ptr as *const u64, len).reduce_m61_parallelized(max_thread_count)
+                }
+                _ => unreachable!("an address has only 16, 32 or 64 bits"),
+            }
         }
     }
 
     #[cfg(feature = "std")]
     #[inline(always)]
-    fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
-        parallelized::reduce_u64(self, max_thread_count)
+    fn reduce_m61_parallelized_with(
+        &self,
+        max_thread_count: usize,
+        strategy: ParallelStrategy,
+    ) -> M61 {
+        // SAFETY: Within the body, we turn the input slice into a
+        // slice of of the same length and with a identically sized type.
+        // Thus, the memory regions are the same.
+        unsafe {
+            use core::slice::from_raw_parts;
+            let (ptr, len) = (self.as_ptr(), self.len());
+            let t = max_thread_count;
+            match core::mem::size_of::<usize>() {
+                2 => {
+                    from_raw_parts(ptr as *const u16, len).reduce_m61_parallelized_with(t, strategy)
+                }
+                4 => {
+                    from_raw_parts(ptr as *const u32, len).reduce_m61_parallelized_with(t, strategy)
+                }
+                8 => {
+                    from_raw_parts(ptr as *const u64, len).reduce_m61_parallelized_with(t, strategy)
+                }
+                _ => unreachable!("an address has only 16, 32 or 64 bits"),
+            }
+        }
     }
-}
 
-impl M61Reduction for [usize] {
+    #[cfg(feature = "rayon")]
     #[inline(always)]
-    fn reduce_m61(&self) -> M61 {
+    fn reduce_m61_rayon(&self) -> M61 {
         // SAFETY: Within the body, we turn the input slice into a
         // slice of of the same length and with a identically sized type.
         // Thus, the memory regions are the same.
@@ -183,17 +562,549 @@ impl M61Reduction for [usize] {
             use core::slice::from_raw_parts;
             let (ptr, len) = (self.as_ptr(), self.len());
             match core::mem::size_of::<usize>() {
-                2 => from_raw_parts(ptr as *const u16, len).reduce_m61(),
-                4 => from_raw_parts(ptr as *const u32, len).reduce_m61(),
-                8 => from_raw_parts(ptr as *const u64, len).reduce_m61(),
+                2 => from_raw_parts(ptr as *const u16, len).reduce_m61_rayon(),
+                4 => from_raw_parts(ptr as *const u32, len).reduce_m61_rayon(),
+                8 => from_raw_parts(ptr as *const u64, len).reduce_m61_rayon(),
                 _ => unreachable!("an address has only 16, 32 or 64 bits"),
             }
         }
     }
+}
+
+/// Helper macro for implementing [`M61Reduction`] for
+/// the primitive signed integer types up to 64 bits.
+///
+/// A negative digit `d` is stored as `d + 2^BITS`, so the reduction of
+/// the unsigned interpretation exceeds the signed one by `2^BITS` times the
+/// number whose digits are the sign bits. The latter is reduced separately.
+macro_rules! make_signed_reduction_impl {
+    ($type:ty, $unsigned:ty) => {
+        /// Treats every digit as signed, i.e. the slice `s` represents
+        /// the sum of `s[i] * 2^(i * BITS)` in the field, so negative
+        /// digits borrow from the more significant ones. This differs from
+        /// the implementation for `[i128]`, which uses two's complement.
+        ///
+        /// ```
+        /// use m61_modulus::*;
+        ///
+        #[doc = concat!("let s: [", stringify!($type), "; 2] = [-1, 1];")]
+        #[doc = concat!("let base = M61::from(1u128 << ", stringify!($type), "::BITS);")]
+        /// assert_eq!(s.reduce_m61(), base - M61::ONE);
+        /// ```
+        impl M61Reduction for [$type] {
+            #[inline]
+            fn reduce_m61(&self) -> M61 {
+                let s = signed_slice!(self, $unsigned);
+                let signs = reduce_mapped(s, |x| x >> (<$unsigned>::BITS - 1), <$unsigned>::BITS);
+                s.reduce_m61() - signs * POW2_MOD[<$unsigned>::BITS as usize % 61]
+            }
+
+            #[inline]
+            fn reduce_m61_scalar(&self) -> M61 {
+                let base = POW2_MOD[<$unsigned>::BITS as usize % 61];
+                self.iter()
+                    .rev()
+                    .fold(M61(0), |acc, &x| acc * base + M61::from(x as i64))
+            }
+
+            #[inline]
+            fn reduce_m61_be(&self) -> M61 {
+                let base = POW2_MOD[<$unsigned>::BITS as usize % 61];
+                self.iter()
+                    .fold(M61(0), |acc, &x| acc * base + M61::from(x as i64))
+            }
+
+            #[inline]
+            fn reduce_m61_bitrev(&self) -> M61 {
+                // The sign bit of a reversed digit is its lowest bit.
+                let s = signed_slice!(self, $unsigned);
+                let reversed = reduce_mapped(s, <$unsigned>::reverse_bits, <$unsigned>::BITS);
+                let signs = reduce_mapped(s, |x| x & 1, <$unsigned>::BITS);
+                reversed - signs * POW2_MOD[<$unsigned>::BITS as usize % 61]
+            }
+
+            // The sign bits are reduced on the current thread,
+            // while the reduction of the digits is parallelized.
+
+            #[cfg(feature = "std")]
+            #[inline]
+            fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
+                let s = signed_slice!(self, $unsigned);
+                let signs = reduce_mapped(s, |x| x >> (<$unsigned>::BITS - 1), <$unsigned>::BITS);
+                s.reduce_m61_parallelized(max_thread_count)
+                    - signs * POW2_MOD[<$unsigned>::BITS as usize % 61]
+            }
+
+            #[cfg(feature = "std")]
+            #[inline]
+            fn reduce_m61_parallelized_with(
+                &self,
+                max_thread_count: usize,
+                strategy: ParallelStrategy,
+            ) -> M61 {
+                let s = signed_slice!(self, $unsigned);
+                let signs = reduce_mapped(s, |x| x >> (<$unsigned>::BITS - 1), <$unsigned>::BITS);
+                s.reduce_m61_parallelized_with(max_thread_count, strategy)
+                    - signs * POW2_MOD[<$unsigned>::BITS as usize % 61]
+            }
+
+            #[cfg(feature = "rayon")]
+            #[inline]
+            fn reduce_m61_rayon(&self) -> M61 {
+                let s = signed_slice!(self, $unsigned);
+                let signs = reduce_mapped(s, |x| x >> (<$unsigned>::BITS - 1), <$unsigned>::BITS);
+                s.reduce_m61_rayon() - signs * POW2_MOD[<$unsigned>::BITS as usize % 61]
+            }
+        }
+    };
+}
+
+/// Reinterprets a slice of signed integers as
+/// a slice of their unsigned counterparts.
+macro_rules! signed_slice {
+    ($s:expr, $unsigned:ty) => {{
+        let s = $s;
+        // SAFETY: Signed and unsigned integers of the same size have the same
+        // layout, and every bit pattern is valid for both of them.
+        unsafe { core::slice::from_raw_parts(s.as_ptr().cast::<$unsigned>(), s.len()) }
+    }};
+}
+
+make_signed_reduction_impl!(i8, u8);
+make_signed_reduction_impl!(i16, u16);
+make_signed_reduction_impl!(i32, u32);
+make_signed_reduction_impl!(i64, u64);
+
+/// Helper macro for implementing [`M61Reduction`] for slices of
+/// `#[repr(transparent)]` wrappers around the primitive unsigned integer
+/// types, reinterpreting them as slices of the wrapped type.
+macro_rules! make_transparent_reduction_impl {
+    ($wrapper:ty, $type:ty) => {
+        const _: () = assert!(
+            core::mem::size_of::<$wrapper>() == core::mem::size_of::<$type>()
+                && core::mem::align_of::<$wrapper>() == core::mem::align_of::<$type>()
+        );
+
+        impl M61Reduction for [$wrapper] {
+            #[inline(always)]
+            fn reduce_m61(&self) -> M61 {
+                transparent_slice!(self, $type).reduce_m61()
+            }
+
+            #[inline(always)]
+            fn reduce_m61_scalar(&self) -> M61 {
+                transparent_slice!(self, $type).reduce_m61_scalar()
+            }
+
+            #[inline(always)]
+            fn reduce_m61_be(&self) -> M61 {
+                transparent_slice!(self, $type).reduce_m61_be()
+            }
+
+            #[inline(always)]
+            fn reduce_m61_bitrev(&self) -> M61 {
+                transparent_slice!(self, $type).reduce_m61_bitrev()
+            }
+
+            #[cfg(feature = "std")]
+            #[inline(always)]
+            fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
+                transparent_slice!(self, $type).reduce_m61_parallelized(max_thread_count)
+            }
+
+            #[cfg(feature = "std")]
+            #[inline(always)]
+            fn reduce_m61_parallelized_with(
+                &self,
+                max_thread_count: usize,
+                strategy: ParallelStrategy,
+            ) -> M61 {
+                transparent_slice!(self, $type)
+                    .reduce_m61_parallelized_with(max_thread_count, strategy)
+            }
+
+            #[cfg(feature = "rayon")]
+            #[inline(always)]
+            fn reduce_m61_rayon(&self) -> M61 {
+                transparent_slice!(self, $type).reduce_m61_rayon()
+            }
+        }
+    };
+}
+
+/// Reinterprets a slice of a `#[repr(transparent)]` wrapper
+/// as a slice of the wrapped type.
+macro_rules! transparent_slice {
+    ($s:expr, $type:ty) => {{
+        let s = $s;
+        // SAFETY: The wrapper is `#[repr(transparent)]` over `$type`, which
+        // is checked to have the same layout. Every valid wrapper value is
+        // a valid value of `$type`, so the memory region can be reinterpreted.
+        unsafe { core::slice::from_raw_parts(s.as_ptr().cast::<$type>(), s.len()) }
+    }};
+}
+
+make_transparent_reduction_impl!(Wrapping<u8>, u8);
+make_transparent_reduction_impl!(Wrapping<u16>, u16);
+make_transparent_reduction_impl!(Wrapping<u32>, u32);
+make_transparent_reduction_impl!(Wrapping<u64>, u64);
+make_transparent_reduction_impl!(Wrapping<usize>, usize);
+make_transparent_reduction_impl!(NonZeroU8, u8);
+make_transparent_reduction_impl!(NonZeroU16, u16);
+make_transparent_reduction_impl!(NonZeroU32, u32);
+make_transparent_reduction_impl!(NonZeroU64, u64);
+make_transparent_reduction_impl!(NonZeroUsize, usize);
+
+/// Reinterprets a slice of limb blocks as a flat slice of limbs.
+#[inline(always)]
+fn flatten_blocks<const N: usize>(s: &[[u64; N]]) -> &[u64] {
+    // SAFETY: `[u64; N]` consists of `N` consecutive `u64` without padding,
+    // so the memory region of the input contains exactly `N * s.len()` limbs.
+    unsafe { core::slice::from_raw_parts(s.as_ptr().cast::<u64>(), N * s.len()) }
+}
+
+/// Treats the blocks as a flat stream of limbs. Both the limbs
+/// within a block and the blocks themselves are in little-endian
+/// ordering, i.e. the result is the same as for `self.concat()`.
+impl<const N: usize> M61Reduction for [[u64; N]] {
+    #[inline(always)]
+    fn reduce_m61(&self) -> M61 {
+        flatten_blocks(self).reduce_m61()
+    }
+
+    #[inline(always)]
+    fn reduce_m61_scalar(&self) -> M61 {
+        flatten_blocks(self).reduce_m61_scalar()
+    }
+
+    /// Treats the flat stream of limbs as big-endian, i.e. both the
+    /// limbs within a block and the blocks themselves are reversed.
+    #[inline(always)]
+    fn reduce_m61_be(&self) -> M61 {
+        flatten_blocks(self).reduce_m61_be()
+    }
+
+    #[inline(always)]
+    fn reduce_m61_bitrev(&self) -> M61 {
+        flatten_blocks(self).reduce_m61_bitrev()
+    }
 
     #[cfg(feature = "std")]
     #[inline(always)]
     fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
+        flatten_blocks(self).reduce_m61_parallelized(max_thread_count)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn reduce_m61_parallelized_with(
+        &self,
+        max_thread_count: usize,
+        strategy: ParallelStrategy,
+    ) -> M61 {
+        flatten_blocks(self).reduce_m61_parallelized_with(max_thread_count, strategy)
+    }
+
+    #[cfg(feature = "rayon")]
+    #[inline(always)]
+    fn reduce_m61_rayon(&self) -> M61 {
+        flatten_blocks(self).reduce_m61_rayon()
+    }
+}
+
+/// Reinterprets `i128` limbs as the `u64` halves of their unsigned
+/// counterparts. On little-endian targets, the less significant
+/// half is stored first, so the halves are in little-endian ordering.
+#[cfg(target_endian = "little")]
+#[inline(always)]
+fn split_i128(s: &[i128]) -> &[u64] {
+    // SAFETY: `i128` consists of two `u64` without padding, and its
+    // alignment is at least the one of `u64`.
+    unsafe { core::slice::from_raw_parts(s.as_ptr().cast::<u64>(), 2 * s.len()) }
+}
+
+/// Reduces the unsigned interpretation of the limbs `s` using the
+/// portable scalar implementation, applying `map` to every limb first.
+fn reduce_i128_scalar(s: &[i128], map: fn(u128) -> u128) -> M61 {
+    use crate::definition::{final_reduction, MODULUS};
+
+    let mut hi = 0;
+    for x in s.iter().rev() {
+        let x = map(*x as u128);
+        for lo in [(x >> 64) as u64, x as u64] {
+            hi = (lo & MODULUS) + (lo >> 61) + ((hi & (MODULUS >> 3)) << 3) + (hi >> 58);
+        }
+    }
+
+    final_reduction(hi)
+}
+
+/// Turns the reduction `unsigned` of the unsigned interpretation of `len`
+/// two's complement limbs into the reduction of the signed number. If it is
+/// negative, the unsigned interpretation exceeds it by `2^(128 len)`.
+#[inline]
+fn apply_sign_i128(unsigned: M61, len: usize, negative: bool) -> M61 {
+    if negative {
+        unsigned - POW2_MOD[definition::shift_exponent(len, 128)]
+    } else {
+        unsigned
+    }
+}
+
+/// Reduces the unsigned interpretation of `s` using `reduce` on little-endian
+/// targets, and the scalar implementation otherwise, before applying the sign.
+#[inline]
+fn reduce_i128_with(s: &[i128], reduce: impl FnOnce(&[u64]) -> M61) -> M61 {
+    #[cfg(target_endian = "little")]
+    let unsigned = reduce(split_i128(s));
+    #[cfg(not(target_endian = "little"))]
+    let unsigned = {
+        let _ = reduce;
+        reduce_i128_scalar(s, |x| x)
+    };
+
+    apply_sign_i128(unsigned, s.len(), s.last().is_some_and(|&x| x < 0))
+}
+
+/// Treats the slice as a little-endian number in two's complement,
+/// i.e. the sign bit of the most significant limb determines the sign
+/// of the whole number, while all other limbs are taken as unsigned.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// // `-1` in two's complement, regardless of the number of limbs.
+/// assert_eq!([-1i128, -1].reduce_m61(), M61::ZERO - M61::ONE);
+/// // `2^128 - 1`, since the most significant limb is positive.
+/// assert_eq!([-1i128, 0].reduce_m61(), [u64::MAX, u64::MAX].reduce_m61());
+/// ```
+impl M61Reduction for [i128] {
+    #[inline]
+    fn reduce_m61(&self) -> M61 {
+        reduce_i128_with(self, |s| s.reduce_m61())
+    }
+
+    #[inline]
+    fn reduce_m61_scalar(&self) -> M61 {
+        let negative = self.last().is_some_and(|&x| x < 0);
+        apply_sign_i128(reduce_i128_scalar(self, |x| x), self.len(), negative)
+    }
+
+    /// The sign bit of the first limb determines the sign.
+    #[inline]
+    fn reduce_m61_be(&self) -> M61 {
+        let negative = self.first().is_some_and(|&x| x < 0);
+        // SAFETY: `i128` and `u128` have the same layout,
+        // and every bit pattern is valid for both of them.
+        let s = unsafe { core::slice::from_raw_parts(self.as_ptr().cast::<u128>(), self.len()) };
+        apply_sign_i128(fallback::be::reduce_u128(s), self.len(), negative)
+    }
+
+    #[inline]
+    fn reduce_m61_bitrev(&self) -> M61 {
+        // The sign bit of the reversed most significant limb is its lowest bit.
+        let negative = self.last().is_some_and(|&x| x & 1 != 0);
+        apply_sign_i128(
+            reduce_i128_scalar(self, u128::reverse_bits),
+            self.len(),
+            negative,
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
+        reduce_i128_with(self, |s| s.reduce_m61_parallelized(max_thread_count))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn reduce_m61_parallelized_with(
+        &self,
+        max_thread_count: usize,
+        strategy: ParallelStrategy,
+    ) -> M61 {
+        reduce_i128_with(self, |s| {
+            s.reduce_m61_parallelized_with(max_thread_count, strategy)
+        })
+    }
+
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn reduce_m61_rayon(&self) -> M61 {
+        reduce_i128_with(self, |s| s.reduce_m61_rayon())
+    }
+}
+
+/// Reinterprets `u128` limbs as their `u64` halves, which
+/// are in little-endian ordering on little-endian targets.
+#[cfg(target_endian = "little")]
+#[inline(always)]
+fn split_u128(s: &[u128]) -> &[u64] {
+    // SAFETY: `u128` consists of two `u64` without padding, and its
+    // alignment is at least the one of `u64`.
+    unsafe { core::slice::from_raw_parts(s.as_ptr().cast::<u64>(), 2 * s.len()) }
+}
+
+/// Reduces `s` using `reduce` on its halves on little-endian
+/// targets, and the scalar implementation otherwise.
+#[inline]
+fn reduce_u128_with(s: &[u128], reduce: impl FnOnce(&[u64]) -> M61) -> M61 {
+    #[cfg(target_endian = "little")]
+    return reduce(split_u128(s));
+    #[cfg(not(target_endian = "little"))]
+    {
+        let _ = reduce;
+        fallback::reduce_u128(s)
+    }
+}
+
+/// Treats every limb as two 64-bit digits in little-endian ordering.
+/// On little-endian targets, the limbs are reinterpreted as a slice
+/// of `u64`, which uses the vectorized implementations.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// assert_eq!([1u128 << 64, 2].reduce_m61(), [0u64, 1, 2, 0].reduce_m61());
+/// ```
+impl M61Reduction for [u128] {
+    #[inline]
+    fn reduce_m61(&self) -> M61 {
+        reduce_u128_with(self, |s| s.reduce_m61())
+    }
+
+    #[inline]
+    fn reduce_m61_scalar(&self) -> M61 {
+        fallback::reduce_u128(self)
+    }
+
+    #[inline]
+    fn reduce_m61_be(&self) -> M61 {
+        fallback::be::reduce_u128(self)
+    }
+
+    #[inline]
+    fn reduce_m61_bitrev(&self) -> M61 {
+        reduce_mapped(self, u128::reverse_bits, u128::BITS)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn reduce_m61_parallelized(&self, max_thread_count: usize) -> M61 {
+        reduce_u128_with(self, |s| s.reduce_m61_parallelized(max_thread_count))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn reduce_m61_parallelized_with(
+        &self,
+        max_thread_count: usize,
+        strategy: ParallelStrategy,
+    ) -> M61 {
+        reduce_u128_with(self, |s| {
+            s.reduce_m61_parallelized_with(max_thread_count, strategy)
+        })
+    }
+
+    #[cfg(feature = "rayon")]
+    #[inline]
+    fn reduce_m61_rayon(&self) -> M61 {
+        reduce_u128_with(self, |s| s.reduce_m61_rayon())
+    }
+}
+
+/// Conversion of values into their reduction modulo `2^61 - 1`.
+///
+/// This unifies the [`From`] implementations of [`M61`] for scalars and
+/// [`M61Reduction::reduce_m61`] for slices, allowing generic code to
+/// accept either of them.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// fn check<T: IntoM61>(value: T, expected: u64) -> bool {
+///     value.into_m61() == expected
+/// }
+///
+/// assert!(check(5u8, 5));
+/// assert!(check(-1i32, (1 << 61) - 2));
+/// assert!(check(&[1u32, 1][..], (1 << 32) + 1));
+/// ```
+pub trait IntoM61 {
+    /// Reduces `self` modulo `2^61 - 1`.
+    #[must_use]
+    fn into_m61(self) -> M61;
+}
+
+/// Helper macro for implementing [`IntoM61`]
+/// using the existing [`From`] implementations.
+macro_rules! make_into_m61_impl {
+    ($($type:ty),*) => {
+        $(
+            impl IntoM61 for $type {
+                #[inline(always)]
+                fn into_m61(self) -> M61 {
+                    M61::from(self)
+                }
+            }
+        )*
+    };
+}
+
+make_into_m61_impl!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl IntoM61 for M61 {
+    #[inline(always)]
+    fn into_m61(self) -> M61 {
+        self
+    }
+}
+
+impl<T> IntoM61 for &[T]
+where
+    [T]: M61Reduction,
+{
+    #[inline(always)]
+    fn into_m61(self) -> M61 {
+        self.reduce_m61()
+    }
+}
+
+/// Helper trait for calculating reductions modulo `2^31 - 1`
+/// using the dot operator.
+///
+/// Only portable scalar implementations are provided.
+///
+/// This trait is sealed, i.e. it can't be implemented outside of this crate.
+pub trait M31Reduction: sealed::Sealed {
+    /// Calculates `self mod (2^31 - 1)`, assuming `self` is a number
+    /// base `2^Self::BITS`, with digits stored in little-endian ordering.
+    #[must_use]
+    fn reduce_m31(&self) -> M31;
+}
+
+/// Helper macro for implementing [`M31Reduction`]
+/// for the primitive unsigned integer types.
+macro_rules! make_m31_reduction_impl {
+    ($type:ty, $func:ident) => {
+        impl M31Reduction for [$type] {
+            #[inline(always)]
+            fn reduce_m31(&self) -> M31 {
+                m31::$func(self)
+            }
+        }
+    };
+}
+
+make_m31_reduction_impl!(u8, reduce_u8);
+make_m31_reduction_impl!(u16, reduce_u16);
+make_m31_reduction_impl!(u32, reduce_u32);
+make_m31_reduction_impl!(u64, reduce_u64);
+
+impl M31Reduction for [usize] {
+    #[inline(always)]
+    fn reduce_m31(&self) -> M31 {
         // SAFETY: Within the body, we turn the input slice into a
         // slice of of the same length and with a identically sized type.
         // Thus, the memory regions are the same.
@@ -201,11 +1112,263 @@ impl M61Reduction for [usize] {
             use core::slice::from_raw_parts;
             let (ptr, len) = (self.as_ptr(), self.len());
             match core::mem::size_of::<usize>() {
-                2 => from_raw_parts(ptr as *const u16, len).reduce_m61_parallelized(max_thread_count),
-                4 => from_raw_parts(ptr as *const u32, len).reduce_m61_parallelized(max_thread_count),
-                8 => from_raw_parts(ptr as *const u64, len).reduce_m61_parallelized(max_thread_count),
+                2 => from_raw_parts(ptr as *const u16, len).reduce_m31(),
+                4 => from_raw_parts(ptr as *const u32, len).reduce_m31(),
+                8 => from_raw_parts(ptr as *const u64, len).reduce_m31(),
                 _ => unreachable!("an address has only 16, 32 or 64 bits"),
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::reference::reference_tests! {
+        reduce_u8_reference(u8) => |s| s.reduce_m61();
+        reduce_u16_reference(u16) => |s| s.reduce_m61();
+        reduce_u32_reference(u32) => |s| s.reduce_m61();
+        reduce_u64_reference(u64) => |s| s.reduce_m61();
+        reduce_usize_reference(usize) => |s| s.reduce_m61();
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_u8_bitrev_correct(v: Vec<u8>) -> bool {
+            let v = v.repeat(7);
+            let reversed: Vec<u8> = v.iter().map(|x| x.reverse_bits()).collect();
+            v.reduce_m61_bitrev() == reversed.reduce_m61()
+        }
+
+        fn reduce_u64_bitrev_correct(v: Vec<u64>) -> bool {
+            let v = v.repeat(7);
+            let reversed: Vec<u64> = v.iter().map(|x| x.reverse_bits()).collect();
+            let blocks: Vec<[u64; 1]> = v.iter().map(|&x| [x]).collect();
+            v.reduce_m61_bitrev() == reversed.reduce_m61()
+                && blocks.reduce_m61_bitrev() == reversed.reduce_m61()
+        }
+
+        fn reduce_usize_bitrev_correct(v: Vec<usize>) -> bool {
+            let reversed: Vec<usize> = v.iter().map(|x| x.reverse_bits()).collect();
+            v.reduce_m61_bitrev() == reversed.reduce_m61()
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_wrapping_correct(v: Vec<u64>) -> bool {
+            let wrapping: Vec<Wrapping<u64>> = v.iter().copied().map(Wrapping).collect();
+            let bytes: Vec<Wrapping<u8>> = v
+                .iter()
+                .flat_map(|x| x.to_le_bytes())
+                .map(Wrapping)
+                .collect();
+            let expected = v.reduce_m61();
+
+            wrapping.reduce_m61() == expected
+                && wrapping.reduce_m61_scalar() == expected
+                && wrapping.reduce_m61_parallelized(4) == expected
+                && bytes.reduce_m61() == expected
+        }
+
+        fn reduce_non_zero_correct(v: Vec<u64>) -> bool {
+            let non_zero: Vec<NonZeroU64> = v.iter().filter_map(|&x| NonZeroU64::new(x)).collect();
+            let raw: Vec<u64> = non_zero.iter().map(|x| x.get()).collect();
+            let expected = raw.reduce_m61();
+
+            non_zero.reduce_m61() == expected && non_zero.reduce_m61_parallelized(4) == expected
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_blocks_correct(v: Vec<(u64, u64, u64, u64)>) -> bool {
+            let blocks4: Vec<[u64; 4]> = v.iter().map(|&(a, b, c, d)| [a, b, c, d]).collect();
+            let blocks2: Vec<[u64; 2]> = blocks4
+                .iter()
+                .flat_map(|&[a, b, c, d]| [[a, b], [c, d]])
+                .collect();
+            let flat = blocks4.concat();
+            let expected = flat.reduce_m61();
+
+            blocks4.reduce_m61() == expected
+                && blocks4.reduce_m61_scalar() == expected
+                && blocks4.reduce_m61_parallelized(4) == expected
+                && blocks2.reduce_m61() == expected
+                && blocks2.reduce_m61_parallelized(4) == expected
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_u8_scalar_correct(slice: Vec<u8>) -> bool {
+            slice.reduce_m61_scalar() == slice.reduce_m61()
+        }
+
+        fn reduce_usize_scalar_correct(slice: Vec<usize>) -> bool {
+            slice.reduce_m61_scalar() == slice.reduce_m61()
+        }
+    }
+
+    /// Reduces two's complement limbs using `num-bigint`.
+    fn reference_reduce_i128(s: &[i128]) -> M61 {
+        use num_bigint::{BigInt, Sign};
+
+        let bytes: Vec<u8> = s.iter().flat_map(|x| x.to_le_bytes()).collect();
+        let (sign, magnitude) = BigInt::from_signed_bytes_le(&bytes).into_parts();
+        let magnitude = crate::reference::reference_reduce(&magnitude.to_bytes_le());
+
+        if sign == Sign::Minus {
+            M61(0) - magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_i128_correct(v: Vec<i128>) -> bool {
+            let expected = reference_reduce_i128(&v);
+
+            v.reduce_m61() == expected
+                && v.reduce_m61_scalar() == expected
+                && v.reduce_m61_parallelized(4) == expected
+        }
+
+        fn reduce_i128_bitrev_correct(v: Vec<i128>) -> bool {
+            let reversed: Vec<i128> = v.iter().map(|x| x.reverse_bits()).collect();
+            v.reduce_m61_bitrev() == reference_reduce_i128(&reversed)
+        }
+    }
+
+    /// Calculates the sum of `s[i] * 2^(i * bits)` element by element.
+    fn reference_reduce_signed<T: Copy + Into<i64>>(s: &[T], bits: u32) -> M61 {
+        let mut result = M61::ZERO;
+        let mut weight = M61::ONE;
+        for &x in s {
+            result += M61::from(x.into()) * weight;
+            weight *= M61::from(1u128 << bits);
+        }
+        result
+    }
+
+    /// Checks every reduction function of a signed slice against the reference.
+    fn check_signed<T: Copy + Into<i64>>(s: &[T], bits: u32, rev: fn(T) -> T) -> bool
+    where
+        [T]: M61Reduction,
+    {
+        let expected = reference_reduce_signed(s, bits);
+        let reversed: Vec<T> = s.iter().map(|&x| rev(x)).collect();
+
+        s.reduce_m61() == expected
+            && s.reduce_m61_scalar() == expected
+            && s.reduce_m61_parallelized(4) == expected
+            && s.reduce_m61_bitrev() == reference_reduce_signed(&reversed, bits)
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_i8_correct(v: Vec<i8>) -> bool {
+            check_signed(&v, 8, i8::reverse_bits)
+        }
+
+        fn reduce_i16_correct(v: Vec<i16>) -> bool {
+            check_signed(&v, 16, i16::reverse_bits)
+        }
+
+        fn reduce_i32_correct(v: Vec<i32>) -> bool {
+            check_signed(&v, 32, i32::reverse_bits)
+        }
+
+        fn reduce_i64_correct(v: Vec<i64>) -> bool {
+            check_signed(&v, 64, i64::reverse_bits)
+        }
+    }
+
+    /// Checks that the big-endian reduction of `s` equals the
+    /// reduction of `s` with the order of its digits reversed.
+    fn check_be<T: Copy>(s: &[T]) -> bool
+    where
+        [T]: M61Reduction,
+    {
+        let reversed: Vec<T> = s.iter().rev().copied().collect();
+        s.reduce_m61_be() == reversed.reduce_m61()
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_be_unsigned(
+            a: Vec<u8>,
+            b: Vec<u16>,
+            c: Vec<u32>,
+            d: Vec<u64>,
+            e: Vec<usize>
+        ) -> bool {
+            check_be(&a) && check_be(&b) && check_be(&c) && check_be(&d) && check_be(&e)
+        }
+
+        fn reduce_be_signed(a: Vec<i8>, b: Vec<i16>, c: Vec<i32>, d: Vec<i64>) -> bool {
+            check_be(&a) && check_be(&b) && check_be(&c) && check_be(&d)
+        }
+
+        fn reduce_be_wide(a: Vec<u128>, b: Vec<i128>) -> bool {
+            check_be(&a) && check_be(&b)
+        }
+
+        fn reduce_be_wrappers(v: Vec<u32>) -> bool {
+            let wrapping: Vec<Wrapping<u32>> = v.iter().copied().map(Wrapping).collect();
+            let blocks: Vec<[u64; 2]> = v.iter().map(|&x| [x as u64, !x as u64]).collect();
+            let flat: Vec<u64> = blocks.concat();
+
+            check_be(&wrapping) && blocks.reduce_m61_be() == flat.reduce_m61_be()
+        }
+    }
+
+    #[test]
+    fn reduce_signed_mixed() {
+        // `5 - 3 * 2^8 + 2^16`
+        let expected = M61::from(5u32) - M61::from(3u32 << 8) + M61::from(1u32 << 16);
+        assert_eq!([5i8, -3, 1].reduce_m61(), expected);
+
+        // A negative digit borrows from the next one: `-1 + 2^64 = 2^64 - 1`.
+        assert_eq!([-1i64, 1].reduce_m61(), M61::from(u64::MAX));
+        assert_eq!([i64::MIN].reduce_m61(), M61::from(i64::MIN));
+
+        let v: Vec<i16> = (0..1000).map(|i| (i * 97 - 30000) as i16).collect();
+        assert!(check_signed(&v, 16, i16::reverse_bits));
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_u128_correct(v: Vec<u128>) -> bool {
+            let halves: Vec<u64> = v.iter().flat_map(|&x| [x as u64, (x >> 64) as u64]).collect();
+            let expected = halves.reduce_m61();
+
+            v.reduce_m61() == expected
+                && v.reduce_m61_scalar() == expected
+                && v.reduce_m61_parallelized(4) == expected
+        }
+
+        fn reduce_u128_bitrev_correct(v: Vec<u128>) -> bool {
+            let reversed: Vec<u128> = v.iter().map(|x| x.reverse_bits()).collect();
+            v.reduce_m61_bitrev() == reversed.reduce_m61()
+        }
+    }
+
+    #[test]
+    fn reduce_i128_boundaries() {
+        for limbs in [
+            &[][..],
+            &[i128::MIN],
+            &[i128::MAX],
+            &[0, i128::MIN],
+            &[-1; 61],
+            &[i128::MIN; 100],
+        ] {
+            assert_eq!(limbs.reduce_m61(), reference_reduce_i128(limbs));
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn into_m61_matches_from(a: u64, b: i128) -> bool {
+            a.into_m61() == M61::from(a) && b.into_m61() == M61::from(b)
+        }
+
+        fn into_m61_matches_reduce(slice: Vec<u16>) -> bool {
+            slice.as_slice().into_m61() == slice.reduce_m61()
+        }
+    }
+}
@@ -0,0 +1,350 @@
+//! Definition of the [`M31`] type as well as basic operations on it.
+//!
+//! The reduction modulo the 31st Mersenne number `2^31 - 1` follows the
+//! same approach as [`crate::fallback`]: Horner's method is performed
+//! base `2^64`, with every step being a digit sum base `2^31`.
+//! Since `2^64 = 2^2 (mod 2^31 - 1)`, the accumulating variable only
+//! has to be multiplied by four in each iteration.
+
+use core::fmt;
+use core::iter;
+use core::ops;
+
+/// The modulus on which arithmetic is performed.
+pub(crate) const MODULUS31: u32 = (1 << 31) - 1;
+
+/// A 32-bit integer in which arithmetic is performed modulo `2^31 - 1`.
+///
+/// This type is useful as a second, independent check besides [`crate::M61`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct M31(pub(crate) u32);
+
+impl M31 {
+    /// Returns the contained value.
+    #[inline(always)]
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+/// Helper macro for the quick generation
+/// of formatting trait implementations.
+macro_rules! make_fmt_impl {
+    ($trait:ident) => {
+        impl fmt::$trait for M31 {
+            #[inline(always)]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                <u32 as fmt::$trait>::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+/// Helper macro for the generation of formatting trait implementations
+/// which append the modulus if the alternate flag is given.
+macro_rules! make_fmt_impl_with_modulus {
+    ($trait:ident) => {
+        impl fmt::$trait for M31 {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                <u32 as fmt::$trait>::fmt(&self.0, f)?;
+                if f.alternate() {
+                    f.write_str(" (mod 2^31-1)")?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+make_fmt_impl_with_modulus!(Display);
+make_fmt_impl_with_modulus!(Debug);
+make_fmt_impl!(LowerHex);
+make_fmt_impl!(UpperHex);
+make_fmt_impl!(Octal);
+make_fmt_impl!(Binary);
+
+/// Reduces a value `x < 2^34` modulo `2^31 - 1`.
+#[inline(always)]
+pub(crate) fn final_reduction(x: u64) -> M31 {
+    let x = (x & MODULUS31 as u64) + (x >> 31);
+    if x >= MODULUS31 as u64 {
+        M31((x - MODULUS31 as u64) as u32)
+    } else {
+        M31(x as u32)
+    }
+}
+
+/// Performs a single iteration of Horner's method base `2^64`,
+/// calculating a value congruent to `hi * 2^64 + lo`.
+/// If `hi < 2^34`, the same holds for the result.
+#[inline(always)]
+pub(crate) fn horner_step(hi: u64, lo: u64) -> u64 {
+    let m = MODULUS31 as u64;
+    let lo = (lo & m) + ((lo >> 31) & m) + (lo >> 62);
+    let hi = (hi & m) + (hi >> 31);
+    lo + (hi << 2)
+}
+
+impl From<u8> for M31 {
+    #[inline(always)]
+    fn from(value: u8) -> Self {
+        Self(value as u32)
+    }
+}
+
+impl From<u16> for M31 {
+    #[inline(always)]
+    fn from(value: u16) -> Self {
+        Self(value as u32)
+    }
+}
+
+impl From<u32> for M31 {
+    #[inline(always)]
+    fn from(value: u32) -> Self {
+        final_reduction(value as u64)
+    }
+}
+
+impl From<u64> for M31 {
+    #[inline(always)]
+    fn from(value: u64) -> Self {
+        final_reduction(horner_step(0, value))
+    }
+}
+
+impl From<usize> for M31 {
+    #[inline(always)]
+    fn from(value: usize) -> Self {
+        Self::from(value as u64)
+    }
+}
+
+/// Helper macro for the quick implementation
+/// of arithmetic operators.
+macro_rules! make_arith_impl {
+    ($trait:ident, $trait_assign:ident, $func:ident, $func_assign:ident, $op:tt, $impl:expr) => {
+        impl ops::$trait for M31 {
+            type Output = Self;
+
+            #[inline]
+            fn $func(self, rhs: Self) -> Self::Output {
+                #[allow(clippy::redundant_closure_call)]
+                $impl(self.0 as u64, rhs.0 as u64)
+            }
+        }
+
+        impl<'a> ops::$trait<&'a M31> for M31 {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $func(self, rhs: &Self) -> Self::Output {
+                self $op *rhs
+            }
+        }
+
+        impl ops::$trait_assign for M31 {
+            #[inline(always)]
+            fn $func_assign(&mut self, rhs: Self) {
+                *self = *self $op rhs
+            }
+        }
+
+        impl<'a> ops::$trait_assign<&'a M31> for M31 {
+            #[inline(always)]
+            fn $func_assign(&mut self, rhs: &Self) {
+                *self = *self $op rhs
+            }
+        }
+    };
+}
+
+make_arith_impl!(Add, AddAssign, add, add_assign, +, |a, b| {
+    final_reduction(a + b)
+});
+make_arith_impl!(Sub, SubAssign, sub, sub_assign, -, |a, b| {
+    final_reduction(a + MODULUS31 as u64 - b)
+});
+make_arith_impl!(Mul, MulAssign, mul, mul_assign, *, |a, b| {
+    final_reduction(horner_step(0, a * b))
+});
+make_arith_impl!(Div, DivAssign, div, div_assign, /, |a, b| {
+    if b == 0 {
+        panic!("attempt to divide by zero");
+    }
+
+    // Calculate the multiplicative inverse using
+    // Fermat's little theorem, `b^(p - 2) = b^(-1) (mod p)`.
+    let mut base = M31(b as u32);
+    let mut exp = MODULUS31 - 2;
+    let mut inv = M31(1);
+    while exp != 0 {
+        if exp & 1 != 0 {
+            inv *= base;
+        }
+        base *= base;
+        exp >>= 1;
+    }
+
+    M31(a as u32) * inv
+});
+
+impl iter::Sum for M31 {
+    #[inline(always)]
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), |a, b| a + b)
+    }
+}
+
+impl<'a> iter::Sum<&'a M31> for M31 {
+    #[inline(always)]
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self(0), |a, b| a + b)
+    }
+}
+
+impl iter::Product for M31 {
+    #[inline(always)]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(1), |a, b| a * b)
+    }
+}
+
+impl<'a> iter::Product<&'a M31> for M31 {
+    #[inline(always)]
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self(1), |a, b| a * b)
+    }
+}
+
+pub(crate) fn reduce_u8(s: &[u8]) -> M31 {
+    let chuncks = s.chunks_exact(8);
+
+    let rem = chuncks.remainder();
+    let mut hi = 0;
+    for x in rem.iter().copied().rev() {
+        hi = (hi << 8) | x as u64;
+    }
+    hi = horner_step(0, hi);
+
+    for lo in chuncks.rev() {
+        let lo = u64::from_le_bytes([lo[0], lo[1], lo[2], lo[3], lo[4], lo[5], lo[6], lo[7]]);
+        hi = horner_step(hi, lo);
+    }
+
+    final_reduction(hi)
+}
+
+pub(crate) fn reduce_u16(s: &[u16]) -> M31 {
+    let chuncks = s.chunks_exact(4);
+
+    let rem = chuncks.remainder();
+    let mut hi = 0;
+    for x in rem.iter().copied().rev() {
+        hi = (hi << 16) | x as u64;
+    }
+    hi = horner_step(0, hi);
+
+    for lo in chuncks.rev() {
+        let lo = (lo[0] as u64)
+            | ((lo[1] as u64) << 16)
+            | ((lo[2] as u64) << 32)
+            | ((lo[3] as u64) << 48);
+        hi = horner_step(hi, lo);
+    }
+
+    final_reduction(hi)
+}
+
+pub(crate) fn reduce_u32(s: &[u32]) -> M31 {
+    let chuncks = s.chunks_exact(2);
+
+    let rem = chuncks.remainder();
+    let mut hi = if let Some(r) = rem.first() {
+        *r as u64
+    } else {
+        0
+    };
+
+    for lo in chuncks.rev() {
+        let lo = lo[0] as u64 | ((lo[1] as u64) << 32);
+        hi = horner_step(hi, lo);
+    }
+
+    final_reduction(hi)
+}
+
+pub(crate) fn reduce_u64(s: &[u64]) -> M31 {
+    let mut hi = 0;
+
+    for lo in s.iter().copied().rev() {
+        hi = horner_step(hi, lo);
+    }
+
+    final_reduction(hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference implementation processing one byte at a time.
+    fn reduce_bytes(bytes: &[u8]) -> M31 {
+        bytes
+            .iter()
+            .rev()
+            .fold(M31(0), |acc, &b| acc * M31(256) + M31::from(b))
+    }
+
+    #[test]
+    fn reduce_max() {
+        for len in 0..100 {
+            let bytes = vec![u8::MAX; len * 8];
+            let expected = reduce_bytes(&bytes);
+
+            assert_eq!(reduce_u8(&bytes), expected);
+            assert_eq!(reduce_u16(&vec![u16::MAX; len * 4]), expected);
+            assert_eq!(reduce_u32(&vec![u32::MAX; len * 2]), expected);
+            assert_eq!(reduce_u64(&vec![u64::MAX; len]), expected);
+        }
+    }
+
+    quickcheck::quickcheck! {
+        fn creation_u64_correct(x: u64) -> bool {
+            M31::from(x).get() as u64 == x % MODULUS31 as u64
+        }
+
+        fn arith_correct(x: u32, y: u32) -> bool {
+            let (a, b) = (x as u64 % MODULUS31 as u64, y as u64 % MODULUS31 as u64);
+            let (mx, my) = (M31::from(x), M31::from(y));
+            let m = MODULUS31 as u64;
+
+            (mx + my).get() as u64 == (a + b) % m
+                && (mx - my).get() as u64 == (a + m - b) % m
+                && (mx * my).get() as u64 == (a * b) % m
+                && (b == 0 || (mx / my) * my == mx)
+        }
+
+        fn reduce_u8_correct(slice: Vec<u8>) -> bool {
+            reduce_u8(&slice) == reduce_bytes(&slice)
+        }
+
+        fn reduce_u16_correct(slice: Vec<u16>) -> bool {
+            let bytes: Vec<u8> = slice.iter().flat_map(|x| x.to_le_bytes()).collect();
+            reduce_u16(&slice) == reduce_bytes(&bytes)
+        }
+
+        fn reduce_u32_correct(slice: Vec<u32>) -> bool {
+            let bytes: Vec<u8> = slice.iter().flat_map(|x| x.to_le_bytes()).collect();
+            reduce_u32(&slice) == reduce_bytes(&bytes)
+        }
+
+        fn reduce_u64_correct(slice: Vec<u64>) -> bool {
+            let bytes: Vec<u8> = slice.iter().flat_map(|x| x.to_le_bytes()).collect();
+            reduce_u64(&slice) == reduce_bytes(&bytes)
+        }
+    }
+}
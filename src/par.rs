@@ -0,0 +1,171 @@
+//! Integration with the `rayon` crate.
+
+use crate::definition::{shift_exponent, M61, POW2_MOD};
+use crate::{implementation, M61Reduction};
+use rayon::prelude::*;
+
+/// The number of elements combined serially by each task.
+/// Chosen large enough to amortize the overhead of scheduling.
+const CHUNK_SIZE: usize = 1 << 14;
+
+/// Calculates the sum of all elements of `s`, distributing
+/// the work over the global `rayon` thread pool.
+///
+/// Since addition is associative and commutative, the result is
+/// the same as `s.iter().sum::<M61>()`.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let v: Vec<M61> = (0..100_000u64).map(M61::from).collect();
+/// assert_eq!(par_sum(&v), v.iter().sum::<M61>());
+/// ```
+#[must_use]
+pub fn par_sum(s: &[M61]) -> M61 {
+    s.par_chunks(CHUNK_SIZE)
+        .map(|chunk| chunk.iter().sum())
+        .reduce(|| M61::ZERO, |a, b| a + b)
+}
+
+/// Calculates the product of all elements of `s`, distributing
+/// the work over the global `rayon` thread pool.
+/// The product of an empty slice is one.
+///
+/// Since multiplication is associative and commutative, the result is
+/// the same as `s.iter().product::<M61>()`.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let v: Vec<M61> = (1..100_000u64).map(M61::from).collect();
+/// assert_eq!(par_product(&v), v.iter().product::<M61>());
+/// ```
+#[must_use]
+pub fn par_product(s: &[M61]) -> M61 {
+    s.par_chunks(CHUNK_SIZE)
+        .map(|chunk| chunk.iter().product())
+        .reduce(|| M61::ONE, |a, b| a * b)
+}
+
+/// Helper macro for the creation of the reductions backing
+/// [`M61Reduction::reduce_m61_rayon`].
+///
+/// The input is split into chunks of `CHUNK_SIZE` digits, which are reduced
+/// as tasks of the current `rayon` thread pool. Like in the `parallelized`
+/// module, the reduction of the chunk with index `i` is shifted by
+/// `i * CHUNK_SIZE * BITS` bits before the results are added up.
+macro_rules! make_function {
+    ($name:ident, $resolve:ident, $type:ty) => {
+        pub(crate) fn $name(s: &[$type]) -> M61 {
+            if s.len() <= CHUNK_SIZE {
+                return s.reduce_m61();
+            }
+
+            // Resolve the implementation once, instead of
+            // going through the dispatch for every chunk.
+            let reduce = implementation::$resolve();
+            let chunk_shift = shift_exponent(CHUNK_SIZE, <$type>::BITS as usize);
+
+            s.par_chunks(CHUNK_SIZE)
+                .enumerate()
+                .map(|(i, chunk)| {
+                    // SAFETY: The resolved implementation is supported by the CPU.
+                    let result = unsafe { reduce(chunk) };
+                    result * POW2_MOD[shift_exponent(i, chunk_shift)]
+                })
+                .reduce(|| M61::ZERO, |a, b| a + b)
+        }
+    };
+}
+
+make_function!(reduce_u8, resolve_u8, u8);
+make_function!(reduce_u16, resolve_u16, u16);
+make_function!(reduce_u32, resolve_u32, u32);
+make_function!(reduce_u64, resolve_u64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_m61(v: &[u64]) -> Vec<M61> {
+        v.iter().copied().map(M61::from).collect()
+    }
+
+    quickcheck::quickcheck! {
+        fn par_sum_matches_serial(v: Vec<u64>) -> bool {
+            let v = to_m61(&v);
+            par_sum(&v) == v.iter().sum::<M61>()
+        }
+
+        fn par_product_matches_serial(v: Vec<u64>) -> bool {
+            let v = to_m61(&v);
+            par_product(&v) == v.iter().product::<M61>()
+        }
+    }
+
+    #[test]
+    fn multiple_chunks() {
+        let v: Vec<u64> = (0..5 * CHUNK_SIZE as u64 + 7)
+            .map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+            .collect();
+        let v = to_m61(&v);
+
+        assert_eq!(par_sum(&v), v.iter().sum::<M61>());
+        assert_eq!(par_product(&v), v.iter().product::<M61>());
+    }
+
+    /// Lengths around the boundaries between chunks.
+    fn lengths() -> impl Iterator<Item = usize> {
+        (0..64).chain((1..4).flat_map(|k| k * CHUNK_SIZE - 1..=k * CHUNK_SIZE + 1))
+    }
+
+    #[test]
+    fn reduce_rayon_matches_serial() {
+        for len in lengths() {
+            let v: Vec<u64> = (0..len as u64)
+                .map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+                .collect();
+            let bytes: Vec<u8> = v.iter().map(|&x| (x >> 56) as u8).collect();
+            let words: Vec<u16> = v.iter().map(|&x| (x >> 48) as u16).collect();
+            let dwords: Vec<u32> = v.iter().map(|&x| (x >> 32) as u32).collect();
+
+            assert_eq!(v.reduce_m61_rayon(), v.reduce_m61(), "len: {len}");
+            assert_eq!(bytes.reduce_m61_rayon(), bytes.reduce_m61(), "len: {len}");
+            assert_eq!(words.reduce_m61_rayon(), words.reduce_m61(), "len: {len}");
+            assert_eq!(dwords.reduce_m61_rayon(), dwords.reduce_m61(), "len: {len}");
+        }
+    }
+
+    #[test]
+    fn reduce_rayon_custom_pool() {
+        let v: Vec<u32> = (0..3 * CHUNK_SIZE as u32 + 5)
+            .map(|x| x.wrapping_mul(7919))
+            .collect();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(3)
+            .build()
+            .unwrap();
+        assert_eq!(pool.install(|| v.reduce_m61_rayon()), v.reduce_m61());
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_rayon_other_digits(v: Vec<i64>, repeat: u8) -> bool {
+            // Repeat the input so that it exceeds the chunk size.
+            let v = v.repeat((repeat as usize % 4 + 1) * 2 * CHUNK_SIZE / (v.len() + 1));
+            let wide: Vec<i128> = v.iter().map(|&x| x as i128 * 3).collect();
+            let unsigned: Vec<u128> = wide.iter().map(|&x| x as u128).collect();
+            let usizes: Vec<usize> = v.iter().map(|&x| x as usize).collect();
+
+            v.reduce_m61_rayon() == v.reduce_m61()
+                && wide.reduce_m61_rayon() == wide.reduce_m61()
+                && unsigned.reduce_m61_rayon() == unsigned.reduce_m61()
+                && usizes.reduce_m61_rayon() == usizes.reduce_m61()
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(par_sum(&[]), M61::ZERO);
+        assert_eq!(par_product(&[]), M61::ONE);
+    }
+}
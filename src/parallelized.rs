@@ -19,9 +19,11 @@
 //! us to simplify the calculation of the powers of `b` by utilizing the
 //! fact that `2^u = 2^v (mod m)` iff `u = v (mod 61)`.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread::{available_parallelism, scope};
 
 use super::*;
+use crate::definition::{shift_exponent, POW2_MOD};
 
 #[cfg(not(test))]
 const THRESHOLD: usize = 1 << 14;
@@ -39,50 +41,158 @@ fn clamp_thread_count(max_thread_count: usize) -> usize {
         .max(1)
 }
 
+/// Determines how the input is split up between threads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParallelStrategy {
+    /// The input is split into one chunk per thread of equal size.
+    #[default]
+    Static,
+    /// The input is split into `factor` chunks per thread. Every thread
+    /// repeatedly takes the next unprocessed chunk from a shared queue
+    /// until none are left, which balances out threads being scheduled
+    /// unevenly. A `factor` of zero is treated as one.
+    OverDecomposed {
+        /// The number of chunks per thread.
+        factor: usize,
+    },
+}
+
 /// Helper macro for the creation of the implementations.
 macro_rules! make_function {
-    ($name:ident, $type:ty) => {
-        pub fn $name(mut s: &[$type], max_thread_count: usize) -> M61 {
+    ($name:ident, $exact:ident, $resolve:ident, $type:ty) => {
+        pub fn $name(s: &[$type], max_thread_count: usize, strategy: ParallelStrategy) -> M61 {
             if s.len() < THRESHOLD {
                 return s.reduce_m61();
             }
 
-            let max_thread_count = clamp_thread_count(max_thread_count);
+            $exact(s, clamp_thread_count(max_thread_count), strategy)
+        }
 
-            scope(|scope| {
-                let mut handles = Vec::with_capacity(max_thread_count);
+        /// Like the function above, but uses `max_thread_count` as is,
+        /// instead of limiting it to the available parallelism. This allows
+        /// testing multi-threaded schedules on hosts with a single core.
+        pub fn $exact(s: &[$type], max_thread_count: usize, strategy: ParallelStrategy) -> M61 {
+            let max_thread_count = max_thread_count.max(1);
+            // If not every thread would receive at least `THRESHOLD` elements,
+            // the overhead of spawning threads exceeds the gain over
+            // the vectorized single-threaded implementation.
+            if s.len() < THRESHOLD.saturating_mul(max_thread_count) {
+                return s.reduce_m61();
+            }
 
-                let mut step = s.len() / max_thread_count;
-                if step < THRESHOLD {
-                    step = THRESHOLD;
+            // Resolve the implementation once, instead of
+            // going through the dispatch for every chunk.
+            let reduce = implementation::$resolve();
+
+            match strategy {
+                ParallelStrategy::Static => split_static!(s, max_thread_count, reduce, $type),
+                ParallelStrategy::OverDecomposed { factor } => {
+                    split_queue!(s, max_thread_count, factor.max(1), reduce, $type)
                 }
+            }
+        }
+    };
+}
 
-                let scale = M61(1 << ((step * <$type>::BITS as usize) % 61));
-                let mut factor = M61::from(1);
+/// Splits `s` into one chunk per thread.
+macro_rules! split_static {
+    ($s:expr, $max_thread_count:expr, $reduce:expr, $type:ty) => {{
+        let mut s = $s;
+        let max_thread_count = $max_thread_count;
+        let reduce = $reduce;
 
-                while s.len() > step {
-                    let (part, rest) = s.split_at(step);
-                    s = rest;
-                    handles.push(scope.spawn(move || part.reduce_m61() * factor));
-                    factor *= scale;
-                }
+        scope(|scope| {
+            let mut step = s.len() / max_thread_count;
+            if step < THRESHOLD {
+                step = THRESHOLD;
+            }
 
-                let mut result = s.reduce_m61() * factor;
+            // One thread is spawned for every chunk except the last one.
+            // Bounding the capacity by the number of chunks prevents
+            // a huge allocation if the thread count is unreasonably large.
+            let mut handles = Vec::with_capacity(max_thread_count.min(s.len() / step));
 
-                for handle in handles {
-                    result += handle.join().expect("thread function is total");
-                }
+            // Every chunk shifts the next one by `step * BITS` bits.
+            let scale = POW2_MOD[shift_exponent(step, <$type>::BITS as usize)];
+            let mut factor = M61::from(1);
 
-                result
-            })
+            while s.len() > step {
+                let (part, rest) = s.split_at(step);
+                s = rest;
+                // SAFETY: The resolved implementation is supported by the CPU.
+                handles.push(scope.spawn(move || unsafe { reduce(part) } * factor));
+                factor *= scale;
+            }
+
+            // SAFETY: The resolved implementation is supported by the CPU.
+            let mut result = unsafe { reduce(s) } * factor;
+
+            // The results of the threads are already shifted by their
+            // position, so combining them only takes one addition per thread.
+            // Waiting for the threads dominates this, so the results are
+            // added up in the order the threads were spawned.
+            for handle in handles {
+                result += handle.join().expect("thread function is total");
+            }
+
+            result
+        })
+    }};
+}
+
+/// Splits `s` into `factor` chunks per thread,
+/// which are distributed using a shared counter.
+macro_rules! split_queue {
+    ($s:expr, $max_thread_count:expr, $factor:expr, $reduce:expr, $type:ty) => {{
+        let s = $s;
+        let max_thread_count = $max_thread_count;
+        let reduce = $reduce;
+
+        let mut step = s.len() / max_thread_count.saturating_mul($factor);
+        if step < THRESHOLD {
+            step = THRESHOLD;
         }
-    };
+
+        let chunk_count = s.len().div_ceil(step);
+        // Every chunk shifts the next one by `step * BITS` bits.
+        let chunk_shift = shift_exponent(step, <$type>::BITS as usize);
+        let next = AtomicUsize::new(0);
+
+        let work = || {
+            let mut result = M61(0);
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= chunk_count {
+                    return result;
+                }
+
+                let part = &s[i * step..s.len().min((i + 1) * step)];
+                let shift = shift_exponent(i, chunk_shift);
+                // SAFETY: The resolved implementation is supported by the CPU.
+                result += unsafe { reduce(part) } * POW2_MOD[shift];
+            }
+        };
+
+        scope(|scope| {
+            let handles: Vec<_> = (1..max_thread_count.min(chunk_count))
+                .map(|_| scope.spawn(work))
+                .collect();
+
+            let mut result = work();
+            for handle in handles {
+                result += handle.join().expect("thread function is total");
+            }
+
+            result
+        })
+    }};
 }
 
-make_function!(reduce_u8, u8);
-make_function!(reduce_u16, u16);
-make_function!(reduce_u32, u32);
-make_function!(reduce_u64, u64);
+make_function!(reduce_u8, reduce_u8_exact, resolve_u8, u8);
+make_function!(reduce_u16, reduce_u16_exact, resolve_u16, u16);
+make_function!(reduce_u32, reduce_u32_exact, resolve_u32, u32);
+make_function!(reduce_u64, reduce_u64_exact, resolve_u64, u64);
 
 #[cfg(test)]
 mod tests {
@@ -93,7 +203,7 @@ mod tests {
     fn reduce_u8_parallelized_correct() {
         for i in 0..1000 {
             let v = vec![1; i];
-            assert_eq!(reduce_u8(&v, 16), v.reduce_m61());
+            assert_eq!(reduce_u8(&v, 16, ParallelStrategy::Static), v.reduce_m61());
         }
     }
 
@@ -102,7 +212,7 @@ mod tests {
     fn reduce_u16_parallelized_correct() {
         for i in 0..1000 {
             let v = vec![1; i];
-            assert_eq!(reduce_u16(&v, 16), v.reduce_m61());
+            assert_eq!(reduce_u16(&v, 16, ParallelStrategy::Static), v.reduce_m61());
         }
     }
 
@@ -111,7 +221,7 @@ mod tests {
     fn reduce_u32_parallelized_correct() {
         for i in 0..1000 {
             let v = vec![1; i];
-            assert_eq!(reduce_u32(&v, 16), v.reduce_m61());
+            assert_eq!(reduce_u32(&v, 16, ParallelStrategy::Static), v.reduce_m61());
         }
     }
 
@@ -120,7 +230,110 @@ mod tests {
     fn reduce_u64_parallelized_correct() {
         for i in 0..1000 {
             let v = vec![1; i];
-            assert_eq!(reduce_u64(&v, 16), v.reduce_m61());
+            assert_eq!(reduce_u64(&v, 16, ParallelStrategy::Static), v.reduce_m61());
         }
     }
+
+    #[cfg_attr(miri, ignore = "the implementation is done using safe Rust")]
+    #[test]
+    fn reduce_over_decomposed_correct() {
+        for i in (0..2000).step_by(7) {
+            let v: Vec<u8> = (0..i).map(|x| (x * 13) as u8).collect();
+            for factor in 0..6 {
+                let strategy = ParallelStrategy::OverDecomposed { factor };
+                assert_eq!(reduce_u8(&v, 4, strategy), v.reduce_m61());
+            }
+
+            let v: Vec<u64> = (0..i)
+                .map(|x| (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+                .collect();
+            for factor in 0..6 {
+                let strategy = ParallelStrategy::OverDecomposed { factor };
+                assert_eq!(reduce_u64(&v, 4, strategy), v.reduce_m61());
+            }
+        }
+    }
+
+    #[cfg_attr(miri, ignore = "the implementation is done using safe Rust")]
+    #[test]
+    fn reduce_extreme_thread_counts() {
+        let v: Vec<u8> = (0..1000).map(|x| (x * 13) as u8).collect();
+        for threads in [0, 1, usize::MAX] {
+            assert_eq!(
+                reduce_u8(&v, threads, ParallelStrategy::Static),
+                v.reduce_m61()
+            );
+        }
+    }
+
+    #[cfg_attr(miri, ignore = "the implementation is done using safe Rust")]
+    #[test]
+    fn reduce_around_crossover() {
+        let threads = clamp_thread_count(usize::MAX);
+        let crossover = THRESHOLD * threads;
+
+        for len in crossover.saturating_sub(3)..crossover + 3 {
+            let v: Vec<u16> = (0..len).map(|x| (x * 7919) as u16).collect();
+            for strategy in [
+                ParallelStrategy::Static,
+                ParallelStrategy::OverDecomposed { factor: 2 },
+            ] {
+                assert_eq!(reduce_u16(&v, threads, strategy), v.reduce_m61());
+            }
+        }
+    }
+
+    #[cfg_attr(miri, ignore = "the implementation is done using safe Rust")]
+    #[test]
+    fn reduce_exact_thread_counts() {
+        // Every thread receives several times the threshold.
+        let v: Vec<u64> = (0..32 * 5 * THRESHOLD as u64 + 3)
+            .map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect();
+        let expected = v.reduce_m61();
+
+        for threads in 1..=32 {
+            for strategy in [
+                ParallelStrategy::Static,
+                ParallelStrategy::OverDecomposed { factor: 3 },
+            ] {
+                assert_eq!(
+                    reduce_u64_exact(&v, threads, strategy),
+                    expected,
+                    "{threads} threads, {strategy:?}"
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(miri, ignore = "the implementation is done using safe Rust")]
+    #[test]
+    fn reduce_independent_of_thread_count() {
+        fn check(v: Vec<u64>, repeat: u8) -> bool {
+            // Repeat the input so that every one of up to 64 threads receives
+            // at least `THRESHOLD` digits, and the work is actually split.
+            let mut v = v;
+            v.push(repeat as u64);
+            let v = v.repeat((64 * THRESHOLD).div_ceil(v.len()) + repeat as usize % 16);
+            let bytes: Vec<u8> = v.iter().flat_map(|x| x.to_le_bytes()).collect();
+            let expected = v.reduce_m61();
+
+            (1..=64).all(|threads| {
+                [
+                    ParallelStrategy::Static,
+                    ParallelStrategy::OverDecomposed { factor: 3 },
+                ]
+                .into_iter()
+                .all(|strategy| {
+                    reduce_u64_exact(&v, threads, strategy) == expected
+                        && reduce_u8_exact(&bytes, threads, strategy) == expected
+                })
+            })
+        }
+
+        // Every case spawns thousands of threads, so fewer cases are run.
+        quickcheck::QuickCheck::new()
+            .tests(20)
+            .quickcheck(check as fn(Vec<u64>, u8) -> bool);
+    }
 }
@@ -0,0 +1,306 @@
+//! Reduction of numbers given as strings, and decoding of stored values.
+
+use core::fmt;
+
+use crate::definition::{M61, MODULUS};
+
+/// The error returned when parsing a number fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseM61Error {
+    /// The string doesn't contain any digits.
+    Empty,
+    /// The string contains a character which isn't a valid digit.
+    InvalidDigit,
+}
+
+impl fmt::Display for ParseM61Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("cannot parse number from empty string"),
+            Self::InvalidDigit => f.write_str("invalid digit found in string"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseM61Error {}
+
+/// The error returned when decoding a stored value fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecodeM61Error {
+    /// The input doesn't consist of exactly eight bytes.
+    InvalidLength,
+    /// The decoded integer isn't smaller than `2^61 - 1`.
+    NonCanonical,
+}
+
+impl fmt::Display for DecodeM61Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => f.write_str("stored value must consist of exactly 8 bytes"),
+            Self::NonCanonical => f.write_str("stored value is not smaller than the modulus"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeM61Error {}
+
+/// Decodes a value stored as its canonical representative
+/// in little-endian byte order, like [`M61::from_le_bytes`].
+///
+/// Unlike [`reduce_m61`](crate::M61Reduction::reduce_m61), this
+/// doesn't accept arbitrary numbers, but validates the input instead.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let bytes = 42u64.to_le_bytes();
+/// assert_eq!(M61::try_from(&bytes[..]), Ok(M61::from(42u32)));
+///
+/// let bytes = u64::MAX.to_le_bytes();
+/// assert_eq!(M61::try_from(&bytes[..]), Err(DecodeM61Error::NonCanonical));
+/// ```
+impl TryFrom<&[u8]> for M61 {
+    type Error = DecodeM61Error;
+
+    #[inline]
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: [u8; 8] = value
+            .try_into()
+            .map_err(|_| DecodeM61Error::InvalidLength)?;
+        let value = u64::from_le_bytes(bytes);
+
+        if value < MODULUS {
+            Ok(M61(value))
+        } else {
+            Err(DecodeM61Error::NonCanonical)
+        }
+    }
+}
+
+/// Calculates `x mod (2^61 - 1)`, where `x` is the number represented
+/// by the hexadecimal string `s`, with the most significant digit first.
+///
+/// The string may be prefixed by `0x` or `0X`. Both lowercase and
+/// uppercase digits are accepted. There is no limit on the number of digits.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let x = reduce_hex("0x10000000000000000");
+///
+/// assert_eq!(x, Ok([0u64, 1].reduce_m61()));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if `s` contains no digits or a character
+/// that isn't a hexadecimal digit.
+pub fn reduce_hex(s: &str) -> Result<M61, ParseM61Error> {
+    let s = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+        .as_bytes();
+
+    if s.is_empty() {
+        return Err(ParseM61Error::Empty);
+    }
+
+    // Every group of 15 digits forms a single digit base `2^60`,
+    // which is then folded into the result using Horner's method.
+    // The first group may be shorter.
+    let scale = M61(1 << 60);
+    let (head, tail) = s.split_at(s.len() % 15);
+
+    let mut result = M61(0);
+    for chunk in core::iter::once(head).chain(tail.chunks_exact(15)) {
+        let mut digit = 0u64;
+        for &c in chunk {
+            let d = (c as char)
+                .to_digit(16)
+                .ok_or(ParseM61Error::InvalidDigit)?;
+            digit = (digit << 4) | d as u64;
+        }
+        result = result * scale + M61(digit);
+    }
+
+    Ok(result)
+}
+
+impl M61 {
+    /// Calculates the reduction of the number represented by the string `s`
+    /// of digits in base `radix`, with the most significant digit first.
+    ///
+    /// Unlike [`u64::from_str_radix`], there is no limit on the number
+    /// of digits, since the result is reduced after every digit.
+    /// Both lowercase and uppercase digits are accepted,
+    /// while signs and prefixes like `0x` aren't.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// assert_eq!(M61::from_str_radix("zz", 36), Ok(M61::from(1295u32)));
+    /// assert_eq!(
+    ///     M61::from_str_radix("10000000000000000", 16),
+    ///     Ok([0u64, 1].reduce_m61()),
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is empty or contains a
+    /// character that isn't a digit in base `radix`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range `2..=36`.
+    #[track_caller]
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseM61Error> {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range 2..=36, got {radix}"
+        );
+
+        if s.is_empty() {
+            return Err(ParseM61Error::Empty);
+        }
+
+        let base = M61(radix as u64);
+        let mut result = M61(0);
+        for c in s.chars() {
+            let d = c.to_digit(radix).ok_or(ParseM61Error::InvalidDigit)?;
+            result = result * base + M61(d as u64);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::M61Reduction;
+
+    quickcheck::quickcheck! {
+        fn try_from_bytes_correct(bytes: Vec<u8>) -> bool {
+            let expected = match <[u8; 8]>::try_from(&bytes[..]) {
+                Ok(array) => M61::from_le_bytes(array).ok_or(DecodeM61Error::NonCanonical),
+                Err(_) => Err(DecodeM61Error::InvalidLength),
+            };
+            M61::try_from(&bytes[..]) == expected
+        }
+    }
+
+    #[test]
+    fn try_from_bytes_boundaries() {
+        let decode = |x: u64| M61::try_from(&x.to_le_bytes()[..]);
+
+        assert_eq!(decode(0), Ok(M61(0)));
+        assert_eq!(decode(MODULUS - 1), Ok(M61(MODULUS - 1)));
+        assert_eq!(decode(MODULUS), Err(DecodeM61Error::NonCanonical));
+        assert_eq!(decode(u64::MAX), Err(DecodeM61Error::NonCanonical));
+        assert_eq!(
+            M61::try_from(&[0u8; 7][..]),
+            Err(DecodeM61Error::InvalidLength)
+        );
+        assert_eq!(
+            M61::try_from(&[0u8; 9][..]),
+            Err(DecodeM61Error::InvalidLength)
+        );
+    }
+
+    quickcheck::quickcheck! {
+        fn reduce_hex_correct(digits: Vec<u64>) -> bool {
+            let mut s = String::from("0x");
+            for d in digits.iter().rev() {
+                s.push_str(&format!("{d:016x}"));
+            }
+            s.push('0');
+
+            // The trailing zero multiplies the number by 16.
+            reduce_hex(&s) == Ok(digits.reduce_m61() * M61(16))
+                && reduce_hex(&s.to_uppercase()[2..]) == Ok(digits.reduce_m61() * M61(16))
+        }
+    }
+
+    #[test]
+    fn reduce_hex_small() {
+        assert_eq!(reduce_hex("0"), Ok(M61(0)));
+        assert_eq!(reduce_hex("0x1f"), Ok(M61(31)));
+        assert_eq!(reduce_hex("1fffffffffffffff"), Ok(M61(0)));
+        assert_eq!(reduce_hex("3fffffffffffffff"), Ok(M61(1)));
+        assert_eq!(
+            reduce_hex("000000000000000000000000000000002a"),
+            Ok(M61(42))
+        );
+    }
+
+    quickcheck::quickcheck! {
+        fn from_str_radix_hex(bytes: Vec<u8>) -> bool {
+            let s: String = bytes.iter().rev().map(|b| format!("{b:02x}")).collect();
+            s.is_empty() || M61::from_str_radix(&s, 16) == Ok(bytes.reduce_m61())
+        }
+
+        fn from_str_radix_matches_u64(x: u64, radix: u32) -> bool {
+            let radix = radix % 35 + 2;
+            let (digits, len) = M61::from(x).to_base_digits(radix);
+            let s: String = digits[..len]
+                .iter()
+                .map(|&d| char::from_digit(d as u32, radix).unwrap())
+                .collect();
+            M61::from_str_radix(&s, radix) == Ok(M61::from(x))
+        }
+    }
+
+    #[test]
+    fn from_str_radix_long() {
+        // 300 hexadecimal digits, i.e. 150 bytes.
+        let bytes: Vec<u8> = (0..150).map(|i| (i * 37 + 11) as u8).collect();
+        let hex: String = bytes.iter().rev().map(|b| format!("{b:02X}")).collect();
+        assert_eq!(M61::from_str_radix(&hex, 16), Ok(bytes.reduce_m61()));
+
+        // The binary representation of the same number.
+        let bin: String = bytes.iter().rev().map(|b| format!("{b:08b}")).collect();
+        assert_eq!(M61::from_str_radix(&bin, 2), Ok(bytes.reduce_m61()));
+    }
+
+    #[test]
+    fn from_str_radix_invalid() {
+        assert_eq!(M61::from_str_radix("", 10), Err(ParseM61Error::Empty));
+        assert_eq!(
+            M61::from_str_radix("12", 2),
+            Err(ParseM61Error::InvalidDigit)
+        );
+        assert_eq!(
+            M61::from_str_radix("0x1f", 16),
+            Err(ParseM61Error::InvalidDigit)
+        );
+        assert_eq!(
+            M61::from_str_radix("-1", 10),
+            Err(ParseM61Error::InvalidDigit)
+        );
+        assert_eq!(
+            M61::from_str_radix("+1", 10),
+            Err(ParseM61Error::InvalidDigit)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be in the range 2..=36")]
+    fn from_str_radix_invalid_radix() {
+        let _ = M61::from_str_radix("1", 37);
+    }
+
+    #[test]
+    fn reduce_hex_invalid() {
+        assert_eq!(reduce_hex(""), Err(ParseM61Error::Empty));
+        assert_eq!(reduce_hex("0x"), Err(ParseM61Error::Empty));
+        assert_eq!(reduce_hex("0xg"), Err(ParseM61Error::InvalidDigit));
+        assert_eq!(reduce_hex("-1"), Err(ParseM61Error::InvalidDigit));
+        assert_eq!(reduce_hex("12 34"), Err(ParseM61Error::InvalidDigit));
+    }
+}
@@ -0,0 +1,171 @@
+//! Precomputed tables for repeated exponentiation with a fixed base.
+//!
+//! The table stores the powers `b^0, b^1, ..., b^(2^w - 1)` of the base `b`.
+//! An exponent is then processed in windows of `w` bits, starting with the
+//! most significant one. For every window, the intermediate result is squared
+//! `w` times before being multiplied with the table entry corresponding
+//! to the value of the window. Compared to binary exponentiation, this
+//! replaces up to `w` multiplications with a single one.
+
+use crate::definition::M61;
+
+/// A table of precomputed powers allowing for fast
+/// exponentiation of a fixed base using a fixed window size.
+///
+/// The table is stored in a buffer of type `T`, which is either
+/// a `Vec<M61>` allocated by [`PowTable::new`], or a caller-provided
+/// slice passed to [`PowTable::with_buffer`].
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// let table = PowTable::new(M61::from(3u32), 4);
+///
+/// assert_eq!(table.pow(5), M61::from(243u32));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PowTable<T> {
+    table: T,
+    window_bits: u32,
+}
+
+/// Fills `table` with consecutive powers of `base`.
+fn fill(table: &mut [M61], base: M61) {
+    let mut power = M61(1);
+    for entry in table {
+        *entry = power;
+        power *= base;
+    }
+}
+
+#[cfg(feature = "std")]
+impl PowTable<Vec<M61>> {
+    /// Creates a table for exponentiating `base` using
+    /// windows of `window_bits` bits. The table
+    /// contains `2^window_bits` elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_bits` is not in the range `1..=16`.
+    #[must_use]
+    pub fn new(base: M61, window_bits: u32) -> Self {
+        assert!(
+            (1..=16).contains(&window_bits),
+            "window size must be between 1 and 16 bits"
+        );
+
+        let mut table = vec![M61(0); 1 << window_bits];
+        fill(&mut table, base);
+        Self { table, window_bits }
+    }
+}
+
+impl<'a> PowTable<&'a mut [M61]> {
+    /// Creates a table for exponentiating `base`, storing
+    /// the precomputed powers in `buffer`. The window size
+    /// is the binary logarithm of the length of `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `buffer` is not a power of two
+    /// in the range `2..=2^16`.
+    #[must_use]
+    pub fn with_buffer(base: M61, buffer: &'a mut [M61]) -> Self {
+        let len = buffer.len();
+        assert!(
+            len.is_power_of_two() && (2..=1 << 16).contains(&len),
+            "buffer length must be a power of two between 2 and 2^16"
+        );
+
+        fill(buffer, base);
+        Self {
+            table: buffer,
+            window_bits: len.trailing_zeros(),
+        }
+    }
+}
+
+impl<T: AsRef<[M61]>> PowTable<T> {
+    /// Returns the base of the exponentiation.
+    #[inline(always)]
+    #[must_use]
+    pub fn base(&self) -> M61 {
+        self.table.as_ref()[1]
+    }
+
+    /// Calculates `base^exp`. For `exp == 0`, the result is one.
+    #[must_use]
+    pub fn pow(&self, exp: u64) -> M61 {
+        let table = self.table.as_ref();
+        let w = self.window_bits;
+        let mask = (1 << w) - 1;
+
+        // Position of the least significant bit
+        // of the most significant window.
+        let mut shift = (63 / w) * w;
+        let mut result = table[((exp >> shift) & mask) as usize];
+
+        while shift > 0 {
+            shift -= w;
+            for _ in 0..w {
+                result *= result;
+            }
+            result *= table[((exp >> shift) & mask) as usize];
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference implementation using binary exponentiation.
+    fn pow(mut base: M61, mut exp: u64) -> M61 {
+        let mut result = M61(1);
+        while exp != 0 {
+            if exp & 1 != 0 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    quickcheck::quickcheck! {
+        fn pow_table_correct(base: u64, exp: u64) -> bool {
+            let base = M61::from(base);
+            let expected = pow(base, exp);
+
+            (1..=16).all(|w| PowTable::new(base, w).pow(exp) == expected)
+        }
+
+        fn pow_table_buffer_correct(base: u64, exp: u64) -> bool {
+            let base = M61::from(base);
+            let mut buffer = [M61(0); 32];
+            let table = PowTable::with_buffer(base, &mut buffer);
+
+            table.base() == base && table.pow(exp) == pow(base, exp)
+        }
+    }
+
+    #[test]
+    fn pow_table_small() {
+        let table = PowTable::new(M61::from(2u32), 3);
+        for exp in 0..61 {
+            assert_eq!(table.pow(exp), M61(1 << exp));
+        }
+        assert_eq!(table.pow(61), M61(1));
+        assert_eq!(PowTable::new(M61(0), 3).pow(0), M61(1));
+        assert_eq!(PowTable::new(M61(0), 3).pow(1), M61(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow_table_buffer_length() {
+        let mut buffer = [M61(0); 12];
+        let _ = PowTable::with_buffer(M61(3), &mut buffer);
+    }
+}
@@ -0,0 +1,116 @@
+//! Integration with the `rand` crate.
+
+use crate::definition::{M61, MODULUS};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+/// Samples an integer from `0..=2^61 - 1`, i.e. from all 61-bit integers.
+#[inline]
+fn random_61_bits<R: Rng + ?Sized>(rng: &mut R) -> u64 {
+    rng.next_u64() >> 3
+}
+
+impl Distribution<M61> for Standard {
+    /// Samples a value uniformly from `0..2^61 - 1`.
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> M61 {
+        // The only 61-bit integer which is out of range is the modulus
+        // itself, so rejections are exceedingly rare.
+        loop {
+            let x = random_61_bits(rng);
+            if x != MODULUS {
+                return M61(x);
+            }
+        }
+    }
+}
+
+impl M61 {
+    /// Samples a nonzero value uniformly from `1..2^61 - 1`.
+    ///
+    /// Nonzero values are exactly those which are invertible, which makes
+    /// this useful for generating divisors.
+    ///
+    /// ```
+    /// use m61_modulus::*;
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// let x = M61::random_nonzero(&mut rng);
+    ///
+    /// assert_eq!(x / x, M61::from(1u32));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn random_nonzero<R: Rng + ?Sized>(rng: &mut R) -> M61 {
+        // Both zero and the modulus represent zero, and are rejected.
+        loop {
+            let x = random_61_bits(rng);
+            if x != 0 && x != MODULUS {
+                return M61(x);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+
+    quickcheck::quickcheck! {
+        fn random_in_range(initial: u64, increment: u64) -> bool {
+            let mut rng = StepRng::new(initial, increment | 1);
+            (0..16).all(|_| rng.gen::<M61>().get() < MODULUS)
+        }
+
+        fn random_nonzero_in_range(initial: u64, increment: u64) -> bool {
+            let mut rng = StepRng::new(initial, increment | 1);
+            (0..16).all(|_| {
+                let x = M61::random_nonzero(&mut rng).get();
+                x != 0 && x < MODULUS
+            })
+        }
+    }
+
+    #[test]
+    fn random_rejects_modulus() {
+        // Yields the 61-bit integers `2^61 - 1`, `0`, `1`, ...
+        let mut rng = StepRng::new(MODULUS << 3, 8);
+        assert_eq!(rng.gen::<M61>(), M61(0));
+    }
+
+    #[test]
+    fn random_produces_largest_value() {
+        let mut rng = StepRng::new((MODULUS - 1) << 3, 8);
+        assert_eq!(rng.gen::<M61>(), M61(MODULUS - 1));
+        // The next value is the modulus, which is skipped.
+        assert_eq!(rng.gen::<M61>(), M61(0));
+    }
+
+    #[test]
+    fn random_covers_range() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(0x6d36_315f_7261_6e64);
+        let samples: Vec<u64> = (0..100_000).map(|_| rng.gen::<M61>().get()).collect();
+
+        // With `10^5` samples, the maximum is expected to be within
+        // about `2^61 / 10^5` of the upper bound, and the mean close
+        // to the middle of the range.
+        let max = *samples.iter().max().unwrap();
+        assert!(max < MODULUS && max > MODULUS - (MODULUS >> 12));
+
+        let mean = samples.iter().map(|&x| x as f64).sum::<f64>() / samples.len() as f64;
+        assert!((mean / MODULUS as f64 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn random_nonzero_rejects_zero() {
+        let mut rng = StepRng::new(0, 8);
+        assert_eq!(M61::random_nonzero(&mut rng), M61(1));
+
+        let mut rng = StepRng::new(MODULUS << 3, 8);
+        assert_eq!(M61::random_nonzero(&mut rng), M61(1));
+    }
+}
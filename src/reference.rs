@@ -0,0 +1,157 @@
+//! Shared correctness tests for the reduction backends.
+//!
+//! Every backend is compared against [`reference_reduce`], which
+//! performs the reduction using arbitrary-precision arithmetic.
+//! New backends only need to invoke [`reference_tests`] in their
+//! test module to be covered.
+
+use crate::definition::{M61, MODULUS};
+use num_bigint::BigUint;
+
+/// Bytes which are most likely to expose carry and overflow bugs.
+const ALPHABET: [u8; 4] = [0, 1, 254, 255];
+
+/// Inputs up to this length are enumerated exhaustively over [`ALPHABET`].
+const EXHAUSTIVE_LEN: usize = 8;
+
+/// Number of inputs sampled for every length not covered exhaustively.
+const SAMPLES: usize = 32;
+
+/// Reduces a little-endian byte sequence using `num-bigint`.
+pub(crate) fn reference_reduce(s: &[u8]) -> M61 {
+    let modulus = BigUint::from(MODULUS);
+    let digits = (BigUint::from_bytes_le(s) % modulus).to_u64_digits();
+    M61(digits.first().copied().unwrap_or(0))
+}
+
+/// A small xorshift generator, which keeps the tests deterministic.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Compares `reduce` against [`reference_reduce`].
+///
+/// The empty input and single digits with values at the boundaries
+/// of every digit width are tested first. Afterwards, all byte sequences
+/// of length `0..=EXHAUSTIVE_LEN` over [`ALPHABET`] are tested.
+/// Enumerating longer sequences is not feasible, which is why the
+/// lengths up to 16, as well as some lengths around the block sizes of the
+/// SIMD implementations, are sampled: half of the samples are drawn from
+/// [`ALPHABET`], the other half from all bytes.
+///
+/// `reduce` returns `None` for inputs it cannot handle,
+/// e.g. because their length is not a multiple of its digit size.
+pub(crate) fn check_all(reduce: impl Fn(&[u8]) -> Option<M61>) {
+    let check = |input: &[u8]| {
+        if let Some(actual) = reduce(input) {
+            let expected = reference_reduce(input);
+            assert_eq!(expected, actual, "input: {input:?}");
+        }
+    };
+
+    // The empty number and single digits of every width, which the
+    // vectorized implementations handle entirely in their tail paths.
+    if let Some(actual) = reduce(&[]) {
+        assert_eq!(actual, M61(0), "empty input");
+    }
+    for size in [1, 2, 4, 8] {
+        let max = u64::MAX >> (64 - 8 * size);
+        let values = [0, 1, 2, max >> 1, (max >> 1) + 1, max - 1, max];
+        let modulus = [MODULUS - 1, MODULUS, MODULUS + 1];
+
+        for value in values
+            .into_iter()
+            .chain(modulus.into_iter().filter(|&x| x <= max))
+        {
+            check(&value.to_le_bytes()[..size]);
+        }
+    }
+
+    let mut input = Vec::new();
+    for len in 0..=EXHAUSTIVE_LEN {
+        for mut index in 0..ALPHABET.len().pow(len as u32) {
+            input.clear();
+            for _ in 0..len {
+                input.push(ALPHABET[index % ALPHABET.len()]);
+                index /= ALPHABET.len();
+            }
+            check(&input);
+        }
+    }
+
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    let lengths = (EXHAUSTIVE_LEN + 1..=16).chain([
+        31, 32, 33, 63, 64, 65, 127, 128, 129, 255, 256, 257, 511, 512, 513, 1000,
+    ]);
+    for len in lengths {
+        for sample in 0..SAMPLES {
+            input.clear();
+            for _ in 0..len {
+                let x = rng.next();
+                if sample % 2 == 0 {
+                    input.push(ALPHABET[x as usize % ALPHABET.len()]);
+                } else {
+                    input.push(x as u8);
+                }
+            }
+            check(&input);
+        }
+    }
+}
+
+/// Generates tests comparing reduction functions against [`reference_reduce`].
+///
+/// Every entry has the form `name(type) if condition => function;`,
+/// where the condition is optional and `function` has to coerce to
+/// `fn(&[type]) -> M61`. The test does nothing if the condition is false,
+/// which allows skipping backends that the CPU does not support.
+macro_rules! reference_tests {
+    ($($name:ident($type:ty) $(if $cond:expr)? => $reduce:expr;)*) => {
+        $(
+            #[test]
+            fn $name() {
+                $(
+                    if !$cond {
+                        return;
+                    }
+                )?
+
+                const SIZE: usize = core::mem::size_of::<$type>();
+                let reduce: fn(&[$type]) -> $crate::M61 = $reduce;
+
+                $crate::reference::check_all(|bytes| {
+                    let chunks = bytes.chunks_exact(SIZE);
+                    if !chunks.remainder().is_empty() {
+                        return None;
+                    }
+
+                    let digits: Vec<$type> = chunks
+                        .map(|c| <$type>::from_le_bytes(c.try_into().unwrap()))
+                        .collect();
+                    Some(reduce(&digits))
+                });
+            }
+        )*
+    };
+}
+
+pub(crate) use reference_tests;
+
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_reduce_known_values() {
+        assert_eq!(reference_reduce(&[]), M61(0));
+        assert_eq!(reference_reduce(&[42]), M61(42));
+        assert_eq!(reference_reduce(&[0xff; 8]), M61(7));
+        assert_eq!(reference_reduce(&[0, 0, 0, 0, 0, 0, 0, 0x20]), M61(1));
+    }
+}
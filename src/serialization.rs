@@ -0,0 +1,145 @@
+//! Integration with the `serde` crate.
+//!
+//! Values are stored as their canonical representatives. Human-readable
+//! formats receive a decimal string, since values may exceed `2^53`,
+//! above which formats like JSON often lose precision when processed
+//! by tools representing numbers as doubles. Other formats receive
+//! a plain `u64`. Deserialization accepts both and rejects values which
+//! aren't canonical.
+
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::definition::{M61, MODULUS};
+
+impl Serialize for M61 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&self.get())
+        } else {
+            serializer.serialize_u64(self.get())
+        }
+    }
+}
+
+/// Visitor accepting canonical representatives,
+/// either as integers or as decimal strings.
+struct M61Visitor;
+
+impl<'de> Visitor<'de> for M61Visitor {
+    type Value = M61;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an integer smaller than 2^61 - 1, or a string containing one")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<M61, E> {
+        if v < MODULUS {
+            Ok(M61(v))
+        } else {
+            Err(E::invalid_value(de::Unexpected::Unsigned(v), &self))
+        }
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<M61, E> {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<M61, E> {
+        match v.parse::<u64>() {
+            // Reject signs, which are accepted by `u64::from_str`.
+            Ok(x) if x < MODULUS && !v.starts_with('+') => Ok(M61(x)),
+            _ => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for M61 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(M61Visitor)
+        } else {
+            deserializer.deserialize_u64(M61Visitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    #[test]
+    fn readable() {
+        assert_tokens(&M61(0).readable(), &[Token::Str("0")]);
+        assert_tokens(&M61(42).readable(), &[Token::Str("42")]);
+        assert_tokens(
+            &M61(MODULUS - 1).readable(),
+            &[Token::Str("2305843009213693950")],
+        );
+        assert_de_tokens(&M61(42).readable(), &[Token::U64(42)]);
+        assert_de_tokens(&M61(42).readable(), &[Token::I32(42)]);
+    }
+
+    #[test]
+    fn compact() {
+        assert_tokens(&M61(0).compact(), &[Token::U64(0)]);
+        assert_tokens(&M61(MODULUS - 1).compact(), &[Token::U64(MODULUS - 1)]);
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let expected = "an integer smaller than 2^61 - 1, or a string containing one";
+
+        assert_de_tokens_error::<serde_test::Readable<M61>>(
+            &[Token::Str("2305843009213693951")],
+            &format!("invalid value: string \"2305843009213693951\", expected {expected}"),
+        );
+        assert_de_tokens_error::<serde_test::Readable<M61>>(
+            &[Token::Str("+1")],
+            &format!("invalid value: string \"+1\", expected {expected}"),
+        );
+        assert_de_tokens_error::<serde_test::Readable<M61>>(
+            &[Token::I64(-1)],
+            &format!("invalid value: integer `-1`, expected {expected}"),
+        );
+        assert_de_tokens_error::<serde_test::Compact<M61>>(
+            &[Token::U64(MODULUS)],
+            &format!("invalid value: integer `{MODULUS}`, expected {expected}"),
+        );
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        for x in [0, 42, (1 << 53) + 1, MODULUS - 1] {
+            let json = serde_json::to_string(&M61(x)).unwrap();
+            assert_eq!(json, format!("\"{x}\""));
+            assert_eq!(serde_json::from_str::<M61>(&json).unwrap(), M61(x));
+        }
+
+        // Plain numbers written by other tools are accepted as well.
+        assert_eq!(serde_json::from_str::<M61>("42").unwrap(), M61(42));
+    }
+
+    #[test]
+    fn json_rejects_non_canonical() {
+        assert!(serde_json::from_str::<M61>("2305843009213693951").is_err());
+        assert!(serde_json::from_str::<M61>("\"2305843009213693951\"").is_err());
+        assert!(serde_json::from_str::<M61>("18446744073709551615").is_err());
+        assert!(serde_json::from_str::<M61>("-1").is_err());
+        assert!(serde_json::from_str::<M61>("1.5").is_err());
+    }
+
+    #[test]
+    fn beyond_double_precision() {
+        // `2^53 + 1` is the smallest integer which a double can't represent.
+        let x = M61((1 << 53) + 1);
+        assert_tokens(&x.readable(), &[Token::Str("9007199254740993")]);
+        assert_tokens(&x.compact(), &[Token::U64((1 << 53) + 1)]);
+    }
+}
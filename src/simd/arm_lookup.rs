@@ -0,0 +1,159 @@
+//! This module dynamically selects the used function at runtime
+//! on 32-bit arm targets, where NEON isn't guaranteed to be available.
+//!
+//! The implementation mirrors the one of `x86_lookup`: the function pointers
+//! are stored in static variables, which initially point to initializer
+//! variants of the functions. These query the available target features,
+//! overwrite the variables, and call the appropriate implementation.
+//! If NEON is absent, the scalar fallback versions are selected.
+//!
+//! Further backends, like one based on SVE, can be added to [`select`]
+//! once they exist, without changing the rest of the module.
+//!
+//! ## Thread safety
+//!
+//! The same reasoning as for `x86_lookup` applies: all accesses to the
+//! static variables are atomic, and concurrent initializers
+//! store the same pointers.
+
+use super::neon;
+use crate::fallback;
+
+use core::mem::transmute;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::definition::M61;
+
+#[inline(always)]
+fn has_neon() -> bool {
+    std::arch::is_arm_feature_detected!("neon")
+}
+
+// These variables contain fuction pointers to the impementations.
+
+static FUNC8: AtomicPtr<()> = AtomicPtr::new(reduce_u8_init as *mut ());
+static FUNC16: AtomicPtr<()> = AtomicPtr::new(reduce_u16_init as *mut ());
+static FUNC32: AtomicPtr<()> = AtomicPtr::new(reduce_u32_init as *mut ());
+static FUNC64: AtomicPtr<()> = AtomicPtr::new(reduce_u64_init as *mut ());
+
+/// Writes the appropiate versions of the functions into the
+/// static variables.
+unsafe fn select() {
+    if has_neon() {
+        FUNC8.store(neon::reduce_u8 as *mut (), Ordering::Relaxed);
+        FUNC16.store(neon::reduce_u16 as *mut (), Ordering::Relaxed);
+        FUNC32.store(neon::reduce_u32 as *mut (), Ordering::Relaxed);
+        FUNC64.store(neon::reduce_u64 as *mut (), Ordering::Relaxed);
+        return;
+    }
+
+    FUNC8.store(fallback::reduce_u8 as *mut (), Ordering::Relaxed);
+    FUNC16.store(fallback::reduce_u16 as *mut (), Ordering::Relaxed);
+    FUNC32.store(fallback::reduce_u32 as *mut (), Ordering::Relaxed);
+    FUNC64.store(fallback::reduce_u64 as *mut (), Ordering::Relaxed);
+}
+
+// Helper types used to keep calls to `transmute` clean.
+
+type T8 = unsafe fn(&[u8]) -> M61;
+type T16 = unsafe fn(&[u16]) -> M61;
+type T32 = unsafe fn(&[u32]) -> M61;
+type T64 = unsafe fn(&[u64]) -> M61;
+
+// Definition of the starting values of the static variables.
+
+unsafe fn reduce_u8_init(s: &[u8]) -> M61 {
+    select();
+    let func = transmute::<*mut (), T8>(FUNC8.load(Ordering::Relaxed));
+    func(s)
+}
+
+unsafe fn reduce_u16_init(s: &[u16]) -> M61 {
+    select();
+    let func = transmute::<*mut (), T16>(FUNC16.load(Ordering::Relaxed));
+    func(s)
+}
+
+unsafe fn reduce_u32_init(s: &[u32]) -> M61 {
+    select();
+    let func = transmute::<*mut (), T32>(FUNC32.load(Ordering::Relaxed));
+    func(s)
+}
+
+unsafe fn reduce_u64_init(s: &[u64]) -> M61 {
+    select();
+    let func = transmute::<*mut (), T64>(FUNC64.load(Ordering::Relaxed));
+    func(s)
+}
+
+// Definition of the exports.
+
+pub fn force_init() {
+    // SAFETY: `select` only stores pointers to functions
+    // whose target features have been detected.
+    unsafe { select() }
+}
+
+/// Helper macro for the generation of functions returning the selected
+/// implementations, performing the selection first if necessary.
+macro_rules! make_resolve {
+    ($($resolve:ident => $static:ident, $init:ident, $type:ident;)*) => {
+        $(
+            #[allow(dead_code)]
+            pub fn $resolve() -> $type {
+                if $static.load(Ordering::Relaxed) == $init as *mut () {
+                    force_init();
+                }
+
+                // SAFETY: The variable only ever contains
+                // function pointers of the correct type.
+                unsafe { transmute::<*mut (), $type>($static.load(Ordering::Relaxed)) }
+            }
+        )*
+    };
+}
+
+make_resolve! {
+    resolve_u8 => FUNC8, reduce_u8_init, T8;
+    resolve_u16 => FUNC16, reduce_u16_init, T16;
+    resolve_u32 => FUNC32, reduce_u32_init, T32;
+    resolve_u64 => FUNC64, reduce_u64_init, T64;
+}
+
+pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
+    let func = transmute::<*mut (), T8>(FUNC8.load(Ordering::Relaxed));
+    func(s)
+}
+
+pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
+    let func = transmute::<*mut (), T16>(FUNC16.load(Ordering::Relaxed));
+    func(s)
+}
+
+pub unsafe fn reduce_u32(s: &[u32]) -> M61 {
+    let func = transmute::<*mut (), T32>(FUNC32.load(Ordering::Relaxed));
+    func(s)
+}
+
+pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
+    let func = transmute::<*mut (), T64>(FUNC64.load(Ordering::Relaxed));
+    func(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_matches_fallback() {
+        let bytes: Vec<u8> = (0..1000).map(|x| (x * 31) as u8).collect();
+        let words: Vec<u64> = (0..1000u64).map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15)).collect();
+
+        force_init();
+        // SAFETY: The dispatch only selects supported implementations.
+        unsafe {
+            assert_eq!(reduce_u8(&bytes), fallback::reduce_u8(&bytes));
+            assert_eq!(reduce_u64(&words), fallback::reduce_u64(&words));
+        }
+    }
+}
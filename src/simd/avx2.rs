@@ -5,6 +5,21 @@ use core::arch::x86_64::*;
 
 use crate::definition::{final_reduction, M61, MODULUS};
 
+// Makes `super::split_vectors` available to the `forward` module.
+#[cfg(any(test, feature = "bench"))]
+use super::split_vectors;
+
+// SAFETY: Every bit pattern is a valid vector.
+unsafe impl super::Vector for __m256i {}
+
+/// Calculates the reduction of the number whose least significant digits
+/// are stored in the `len` vectors starting at `ptr`, followed by the
+/// most significant digits stored in `hi`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reading `len` consecutive values of type `__m256i`,
+/// without any alignment requirements. AVX2 must be supported by the CPU.
 #[target_feature(enable = "avx2")]
 unsafe fn reduction_core(ptr: *const __m256i, mut len: usize, mut hi: __m256i) -> M61 {
     let mlo = _mm256_set1_epi64x(MODULUS as i64);
@@ -53,111 +68,183 @@ unsafe fn reduction_core(ptr: *const __m256i, mut len: usize, mut hi: __m256i) -
 
 #[target_feature(enable = "avx2")]
 pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
-    let hi = if s.len() & 31 != 0 {
-        let mut lo = _mm_setzero_si128();
-        let mut hi = _mm_setzero_si128();
-
-        let l = s.len() & !31;
-        let mut ptr = s.as_ptr().add(l);
-
-        if s.len() & 16 != 0 {
-            lo = (ptr as *const __m128i).read_unaligned();
-            ptr = ptr.add(16);
-        }
-
-        let mut tmp = _mm_setzero_si128();
-        for i in (0..(s.len() & 15)).rev() {
-            tmp = _mm_bslli_si128::<1>(tmp);
-            tmp = _mm_insert_epi8::<0>(tmp, *ptr.add(i) as i32);
-        }
-
-        if s.len() & 16 != 0 {
-            hi = tmp;
-        } else {
-            lo = tmp;
-        }
+    let (ptr, len, hi) = super::split_vectors::<u8, __m256i, 32>(s);
+    reduction_core(ptr, len, hi)
+}
 
-        _mm256_set_m128i(hi, lo)
-    } else {
-        _mm256_setzero_si256()
-    };
+#[target_feature(enable = "avx2")]
+pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
+    let (ptr, len, hi) = super::split_vectors::<u16, __m256i, 16>(s);
+    reduction_core(ptr, len, hi)
+}
 
-    reduction_core(s.as_ptr() as *const __m256i, s.len() >> 5, hi)
+#[target_feature(enable = "avx2")]
+pub unsafe fn reduce_u32(s: &[u32]) -> M61 {
+    let (ptr, len, hi) = super::split_vectors::<u32, __m256i, 8>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[target_feature(enable = "avx2")]
-pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
-    let hi = if s.len() & 15 != 0 {
-        let mut lo = _mm_setzero_si128();
-        let mut hi = _mm_setzero_si128();
+pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
+    let (ptr, len, hi) = super::split_vectors::<u64, __m256i, 4>(s);
+    reduction_core(ptr, len, hi)
+}
 
-        let l = s.len() & !15;
-        let mut ptr = s.as_ptr().add(l);
+/// Variants traversing the input in ascending order of memory addresses.
+/// See [`crate::fallback::forward`] for the algorithm.
+///
+/// Like the backward traversal, the input is split into four polynomials,
+/// one per lane, with the weights of consecutive vectors differing
+/// by `2^(4 * 64) = 2^12`. Since all lanes share the same weight,
+/// the rotations use a uniform shift amount.
+#[cfg(any(test, feature = "bench"))]
+pub(crate) mod forward {
+    use super::*;
 
-        if s.len() & 8 != 0 {
-            lo = (ptr as *const __m128i).read_unaligned();
-            ptr = ptr.add(8);
-        }
+    /// Multiplies every lane `x < 2^61` by `2^k` for `k < 61`,
+    /// with the results being below `2^61`.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn rotate(x: __m256i, mlo: __m256i, k: u32) -> __m256i {
+        let left = _mm_cvtsi32_si128(k as i32);
+        let right = _mm_cvtsi32_si128(61 - k as i32);
+        _mm256_or_si256(
+            _mm256_and_si256(_mm256_sll_epi64(x, left), mlo),
+            _mm256_srl_epi64(x, right),
+        )
+    }
 
-        let mut tmp = _mm_setzero_si128();
-        for i in (0..(s.len() & 7)).rev() {
-            tmp = _mm_bslli_si128::<2>(tmp);
-            tmp = _mm_insert_epi16::<0>(tmp, *ptr.add(i) as i32);
-        }
+    /// Adds the vector `lo` with weight `2^k` to the accumulator.
+    #[target_feature(enable = "avx2")]
+    #[inline]
+    unsafe fn step(acc: __m256i, lo: __m256i, mlo: __m256i, k: u32) -> __m256i {
+        let acc = _mm256_add_epi64(_mm256_and_si256(acc, mlo), _mm256_srli_epi64::<61>(acc));
+        let lr = rotate(_mm256_and_si256(lo, mlo), mlo, k);
+        let hr = rotate(_mm256_srli_epi64::<61>(lo), mlo, k);
+        _mm256_add_epi64(acc, _mm256_add_epi64(lr, hr))
+    }
 
-        if s.len() & 8 != 0 {
-            hi = tmp;
-        } else {
-            lo = tmp;
+    /// Calculates the reduction of the number whose least significant digits
+    /// are stored in the `len` vectors starting at `ptr`, followed by the
+    /// most significant digits stored in `hi`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reading `len` consecutive values of type `__m256i`,
+    /// without any alignment requirements. AVX2 must be supported by the CPU.
+    #[target_feature(enable = "avx2")]
+    unsafe fn reduction_core(ptr: *const __m256i, len: usize, hi: __m256i) -> M61 {
+        let mlo = _mm256_set1_epi64x(MODULUS as i64);
+        let mut acc = _mm256_setzero_si256();
+        let mut k = 0;
+
+        for i in 0..len {
+            acc = step(acc, ptr.add(i).read_unaligned(), mlo, k);
+            k = if k >= 49 { k - 49 } else { k + 12 };
         }
+        acc = step(acc, hi, mlo, k);
+
+        // The lane with index `j` is offset by `2^(64 j) = 2^(3 j)`.
+        let mut lanes = [0u64; 4];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast::<__m256i>(), acc);
+        lanes
+            .iter()
+            .enumerate()
+            .map(|(j, &x)| M61::from(x) * M61(1 << (3 * j)))
+            .sum()
+    }
 
-        _mm256_set_m128i(hi, lo)
-    } else {
-        _mm256_setzero_si256()
-    };
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
+        let (ptr, len, hi) = super::split_vectors::<u8, __m256i, 32>(s);
+        reduction_core(ptr, len, hi)
+    }
 
-    reduction_core(s.as_ptr() as *const __m256i, s.len() >> 4, hi)
-}
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
+        let (ptr, len, hi) = super::split_vectors::<u16, __m256i, 16>(s);
+        reduction_core(ptr, len, hi)
+    }
 
-#[target_feature(enable = "avx2")]
-pub unsafe fn reduce_u32(s: &[u32]) -> M61 {
-    let hi = if s.len() & 7 != 0 {
-        let mut arr = [0; 8];
-        let l = s.len() & !7;
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn reduce_u32(s: &[u32]) -> M61 {
+        let (ptr, len, hi) = super::split_vectors::<u32, __m256i, 8>(s);
+        reduction_core(ptr, len, hi)
+    }
 
-        for i in l..s.len() {
-            arr[i - l] = *s.get_unchecked(i);
-        }
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
+        let (ptr, len, hi) = super::split_vectors::<u64, __m256i, 4>(s);
+        reduction_core(ptr, len, hi)
+    }
+}
 
-        (arr.as_ptr() as *const __m256i).read_unaligned()
-    } else {
-        _mm256_setzero_si256()
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    reduction_core(s.as_ptr() as *const __m256i, s.len() >> 3, hi)
-}
+    crate::reference::reference_tests! {
+        reduce_u8_forward_reference(u8) if std::arch::is_x86_feature_detected!("avx2") =>
+            |s| unsafe { forward::reduce_u8(s) };
+        reduce_u16_forward_reference(u16) if std::arch::is_x86_feature_detected!("avx2") =>
+            |s| unsafe { forward::reduce_u16(s) };
+        reduce_u32_forward_reference(u32) if std::arch::is_x86_feature_detected!("avx2") =>
+            |s| unsafe { forward::reduce_u32(s) };
+        reduce_u64_forward_reference(u64) if std::arch::is_x86_feature_detected!("avx2") =>
+            |s| unsafe { forward::reduce_u64(s) };
+    }
 
-#[target_feature(enable = "avx2")]
-pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
-    let hi = if s.len() & 3 != 0 {
-        let mut arr = [0; 4];
-        let l = s.len() & !3;
+    #[test]
+    fn forward_matches_backward() {
+        if !std::arch::is_x86_feature_detected!("avx2") {
+            return;
+        }
 
-        for i in l..s.len() {
-            arr[i - l] = *s.get_unchecked(i);
+        for len in 0..1000 {
+            let vec = vec![u64::MAX; len];
+            // SAFETY: AVX2 is supported.
+            unsafe { assert_eq!(forward::reduce_u64(&vec), reduce_u64(&vec)) };
         }
+    }
 
-        (arr.as_ptr() as *const __m256i).read_unaligned()
-    } else {
-        _mm256_setzero_si256()
-    };
+    crate::reference::reference_tests! {
+        reduce_u8_reference(u8) if std::arch::is_x86_feature_detected!("avx2") =>
+            |s| unsafe { reduce_u8(s) };
+        reduce_u16_reference(u16) if std::arch::is_x86_feature_detected!("avx2") =>
+            |s| unsafe { reduce_u16(s) };
+        reduce_u32_reference(u32) if std::arch::is_x86_feature_detected!("avx2") =>
+            |s| unsafe { reduce_u32(s) };
+        reduce_u64_reference(u64) if std::arch::is_x86_feature_detected!("avx2") =>
+            |s| unsafe { reduce_u64(s) };
+    }
 
-    reduction_core(s.as_ptr() as *const __m256i, s.len() >> 2, hi)
-}
+    #[test]
+    fn aligned_and_unaligned_agree() {
+        if !std::arch::is_x86_feature_detected!("avx2") {
+            return;
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let bytes: Vec<u8> = (0..2000u32)
+            .map(|x| x.wrapping_mul(0x9e37_79b9).to_be_bytes()[0])
+            .collect();
+        let words: Vec<u64> = (0..300u64)
+            .map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect();
+        let bytes_start = bytes.as_ptr().align_offset(32);
+        let words_start = words.as_ptr().align_offset(32);
+
+        // Only the first offset is aligned to the vector size.
+        for len in [0, 1, 32 - 1, 32, 32 + 1, 250] {
+            for offset in 0..32 {
+                let s = &bytes[bytes_start + offset..][..len];
+                assert_eq!(unsafe { reduce_u8(s) }, crate::fallback::reduce_u8(s));
+            }
+            for offset in 0..32 / 8 {
+                let s = &words[words_start + offset..][..len];
+                assert_eq!(unsafe { reduce_u64(s) }, crate::fallback::reduce_u64(s));
+            }
+        }
+    }
 
     #[test]
     fn reduce_u8_max() {
@@ -5,6 +5,14 @@ use core::arch::x86_64::*;
 
 use crate::definition::{final_reduction, M61, MODULUS};
 
+/// Calculates the reduction of the number whose least significant digits
+/// are stored in the `len` vectors starting at `ptr`, followed by the
+/// most significant digits stored in `hi`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reading `len` consecutive values of type `__m512i`,
+/// without any alignment requirements. AVX512F must be supported by the CPU.
 #[target_feature(enable = "avx512f")]
 pub unsafe fn reduction_core(ptr: *const __m512i, mut len: usize, mut hi: __m512i) -> M61 {
     let mlo = _mm512_set1_epi64(MODULUS as i64);
@@ -67,7 +75,7 @@ pub unsafe fn reduction_core(ptr: *const __m512i, mut len: usize, mut hi: __m512
     final_reduction(hi)
 }
 
-#[target_feature(enable = "avx512f")]
+#[target_feature(enable = "avx512f,avx512vbmi2")]
 pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
     let hi = if s.len() & 63 != 0 {
         let trailing_count = s.len() & 63;
@@ -80,7 +88,7 @@ pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
     reduction_core(s.as_ptr() as *const __m512i, s.len() >> 6, hi)
 }
 
-#[target_feature(enable = "avx512f")]
+#[target_feature(enable = "avx512f,avx512vbmi2")]
 pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
     let hi = if s.len() & 31 != 0 {
         let trailing_count = s.len() & 31;
@@ -123,9 +131,20 @@ pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
 mod tests {
     use super::*;
 
+    crate::reference::reference_tests! {
+        reduce_u8_reference(u8) if std::arch::is_x86_feature_detected!("avx512vbmi2") =>
+            |s| unsafe { reduce_u8(s) };
+        reduce_u16_reference(u16) if std::arch::is_x86_feature_detected!("avx512vbmi2") =>
+            |s| unsafe { reduce_u16(s) };
+        reduce_u32_reference(u32) if std::arch::is_x86_feature_detected!("avx512f") =>
+            |s| unsafe { reduce_u32(s) };
+        reduce_u64_reference(u64) if std::arch::is_x86_feature_detected!("avx512f") =>
+            |s| unsafe { reduce_u64(s) };
+    }
+
     #[test]
     fn reduce_u8_max() {
-        if !std::arch::is_x86_feature_detected!("avx512f") {
+        if !std::arch::is_x86_feature_detected!("avx512vbmi2") {
             return;
         }
 
@@ -143,7 +162,7 @@ mod tests {
 
     #[test]
     fn reduce_u16_max() {
-        if !std::arch::is_x86_feature_detected!("avx512f") {
+        if !std::arch::is_x86_feature_detected!("avx512vbmi2") {
             return;
         }
 
@@ -197,7 +216,7 @@ mod tests {
 
     quickcheck::quickcheck! {
         fn reduce_u8_correct(slice: Vec<u8>) -> bool {
-            if !std::arch::is_x86_feature_detected!("avx512f") {
+            if !std::arch::is_x86_feature_detected!("avx512vbmi2") {
                 return true;
             }
 
@@ -207,7 +226,7 @@ mod tests {
         }
 
         fn reduce_u16_correct(slice: Vec<u16>) -> bool {
-            if !std::arch::is_x86_feature_detected!("avx512f") {
+            if !std::arch::is_x86_feature_detected!("avx512vbmi2") {
                 return true;
             }
 
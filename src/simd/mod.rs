@@ -22,47 +22,233 @@
 //!
 //! Since the new polynomials are now evaluated at a different point compared to
 //! the original algorithm, binary shifts and masking changes accordingly.
+//!
+//! Each architecture provides a `reduction_core` function operating on a pointer
+//! to `len` vectors and a vector `hi` containing the most significant digits,
+//! which don't fill an entire vector. Both are obtained using [`split_vectors`],
+//! meaning that the public functions only have to pass them on.
+
+/// A digit type which can be loaded into vectors.
+///
+/// # Safety
+///
+/// The type must not contain any padding bytes.
+pub(crate) unsafe trait Lane: Copy + Default {}
+
+// SAFETY: Primitive integers don't contain padding bytes.
+unsafe impl Lane for u8 {}
+unsafe impl Lane for u16 {}
+unsafe impl Lane for u32 {}
+unsafe impl Lane for u64 {}
+
+/// A vector type operated on by one of the `reduction_core` functions.
+///
+/// # Safety
+///
+/// Every bit pattern must be a valid value of the type.
+pub(crate) unsafe trait Vector {}
+
+/// Loads the last `s.len() % N` elements of `s` into the lowest elements
+/// of an otherwise zeroed vector of type `V`. These are the most significant
+/// digits which don't fill an entire vector, with `N` elements fitting into one.
+///
+/// # Safety
+///
+/// `V` must consist of exactly `N` values of type `T`.
+#[cfg_attr(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        avx512_intrinsics,
+        target_feature = "avx512f",
+        target_feature = "avx512vbmi2",
+    ),
+    allow(dead_code)
+)]
+#[inline(always)]
+unsafe fn load_tail<T: Lane, V: Vector, const N: usize>(s: &[T]) -> V {
+    debug_assert_eq!(core::mem::size_of::<V>(), core::mem::size_of::<[T; N]>());
+
+    let mut arr = [T::default(); N];
+    let rem = s.len() % N;
+    arr[..rem].copy_from_slice(&s[s.len() - rem..]);
+    (arr.as_ptr() as *const V).read_unaligned()
+}
+
+/// Splits `s` into vectors of type `V` consisting of `N` digits each.
+///
+/// Returns a pointer to the first vector, the number of complete vectors,
+/// and the most significant digits which don't fill an entire vector,
+/// loaded using [`load_tail`]. These are the arguments expected by the
+/// `reduction_core` functions. The pointer is valid for reading the
+/// returned number of vectors, but isn't necessarily aligned.
+///
+/// Fails to compile if `V` doesn't consist of exactly `N` values of type `T`.
+#[cfg_attr(
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        avx512_intrinsics,
+        target_feature = "avx512f",
+        target_feature = "avx512vbmi2",
+    ),
+    allow(dead_code)
+)]
+#[inline(always)]
+pub(crate) fn split_vectors<T: Lane, V: Vector, const N: usize>(s: &[T]) -> (*const V, usize, V) {
+    const { assert!(core::mem::size_of::<V>() == N * core::mem::size_of::<T>()) };
+
+    // SAFETY: The size of `V` was checked above.
+    let hi = unsafe { load_tail::<T, V, N>(s) };
+    (s.as_ptr().cast::<V>(), s.len() / N, hi)
+}
+
+/// Defines the functions returning the implementations
+/// for backends which are chosen at compile time.
+#[allow(unused_macros)]
+macro_rules! make_direct_resolve {
+    () => {
+        use crate::definition::M61;
+
+        crate::fallback::make_resolve! {
+            resolve_u8 => reduce_u8, u8;
+            resolve_u16 => reduce_u16, u16;
+            resolve_u32 => reduce_u32, u32;
+            resolve_u64 => reduce_u64, u64;
+        }
+    };
+}
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] {
-        // 1. If nightly features are enabled, and the avx512f target feature
-        //    is available by default, use the avx512 version directly.
-        // 2. If the avx512 version can't be used directly, and the avx2
-        //    target feature is available by default, use the avx2 version directly.
-        // 3. Otherwise, use the lookup version which chooses the
+        // 1. If one of the `force-sse2` and `force-avx2` features is enabled,
+        //    use the chosen version directly, with `force-sse2` taking precedence.
+        //    The crate root ensures that the required target feature is available
+        //    by default. `force-scalar` doesn't reach this module at all.
+        // 2. If the AVX512 intrinsics are available, and the avx512f and avx512vbmi2
+        //    target features are available by default, use the avx512 version directly.
+        //    The intrinsics are stable since Rust 1.89, and can be enabled on older
+        //    nightly compilers using the `nightly` feature (see `build.rs`).
+        // 3. If the AVX512 intrinsics aren't available, and the avx2 target feature
+        //    is available by default, use the avx2 version directly.
+        // 4. Otherwise, use the lookup version which chooses the
         //    implementation at runtime.
+        //
+        // If a version is forced, the others are only used for benchmarks.
 
-        #[cfg(not(target_feature = "avx2"))]
+        #[cfg(any(feature = "force-sse2", not(target_feature = "avx2")))]
+        #[cfg_attr(any(feature = "force-sse2", feature = "force-avx2"), allow(dead_code))]
         mod sse2;
-        #[cfg(not(target_feature = "avx512f"))]
-        mod avx2;
-        #[cfg(feature = "nightly")]
-        mod avx512;
         #[cfg(any(
-            all(not(feature = "nightly"), not(target_feature = "avx2")),
-            all(feature = "nightly", not(target_feature = "avx512f")),
+            feature = "force-avx2",
+            not(all(
+                avx512_intrinsics,
+                target_feature = "avx512f",
+                target_feature = "avx512vbmi2",
+            )),
         ))]
-        mod x86_lookup;
+        #[cfg_attr(any(feature = "force-sse2", feature = "force-avx2"), allow(dead_code))]
+        mod avx2;
+        #[cfg(avx512_intrinsics)]
+        #[cfg_attr(any(feature = "force-sse2", feature = "force-avx2"), allow(dead_code))]
+        mod avx512;
 
-        #[cfg(all(feature = "nightly", target_feature = "avx512f"))]
-        pub(crate) use avx512::*;
+        #[cfg(feature = "bench")]
+        pub(crate) fn push_backends(backends: &mut Vec<crate::backend::Backend>) {
+            use crate::backend::backend;
+            use std::arch::is_x86_feature_detected;
 
-        #[cfg(all(not(target_feature = "avx512f"), target_feature = "avx2"))]
-        pub(crate) use avx2::*;
+            // SAFETY: Every backend is only added if the
+            // target features it requires are available.
+            unsafe {
+                #[cfg(any(feature = "force-sse2", not(target_feature = "avx2")))]
+                backends.push(backend!("sse2", sse2));
 
-        #[cfg(any(
-            all(feature = "nightly", not(target_feature = "avx512f")),
-            all(not(feature = "nightly"), not(target_feature = "avx2")),
-        ))]
-        pub(crate) use x86_lookup::*;
+                #[cfg(any(
+                    feature = "force-avx2",
+                    not(all(
+                        avx512_intrinsics,
+                        target_feature = "avx512f",
+                        target_feature = "avx512vbmi2",
+                    )),
+                ))]
+                if is_x86_feature_detected!("avx2") {
+                    backends.push(backend!("avx2", avx2));
+                    backends.push(backend!("avx2-forward", avx2::forward));
+                }
+
+                #[cfg(avx512_intrinsics)]
+                if is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512vbmi2") {
+                    backends.push(backend!("avx512", avx512));
+                }
+            }
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "force-sse2")] {
+                pub(crate) use sse2::*;
+                pub(crate) use crate::fallback::force_init;
+                make_direct_resolve!();
+            } else if #[cfg(feature = "force-avx2")] {
+                pub(crate) use avx2::*;
+                pub(crate) use crate::fallback::force_init;
+                make_direct_resolve!();
+            } else if #[cfg(all(
+                avx512_intrinsics,
+                target_feature = "avx512f",
+                target_feature = "avx512vbmi2",
+            ))] {
+                pub(crate) use avx512::*;
+                pub(crate) use crate::fallback::force_init;
+                make_direct_resolve!();
+            } else if #[cfg(all(not(avx512_intrinsics), target_feature = "avx2"))] {
+                pub(crate) use avx2::*;
+                pub(crate) use crate::fallback::force_init;
+                make_direct_resolve!();
+            } else {
+                mod x86_lookup;
+
+                pub(crate) use x86_lookup::*;
+            }
+        }
     } else if #[cfg(any(target_arch = "arm", target_arch = "aarch64"))] {
+        // NEON is part of the baseline of aarch64, but optional on 32-bit arm.
+        // If it isn't enabled at compile time, it is detected at runtime.
+
         mod neon;
 
-        pub(crate) use neon::*;
+        #[cfg(feature = "bench")]
+        pub(crate) fn push_backends(backends: &mut Vec<crate::backend::Backend>) {
+            #[cfg(all(target_arch = "arm", not(target_feature = "neon")))]
+            if !std::arch::is_arm_feature_detected!("neon") {
+                return;
+            }
+
+            // SAFETY: NEON is either enabled at compile time or has been detected.
+            backends.push(unsafe { crate::backend::backend!("neon", neon) });
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(all(target_arch = "arm", not(target_feature = "neon")))] {
+                mod arm_lookup;
+
+                pub(crate) use arm_lookup::*;
+            } else {
+                pub(crate) use neon::*;
+                pub(crate) use crate::fallback::force_init;
+                make_direct_resolve!();
+            }
+        }
     } else if #[cfg(any(target_family = "wasm"))] {
         mod wasm_simd128;
 
         pub(crate) use wasm_simd128::*;
+        pub(crate) use crate::fallback::force_init;
+        make_direct_resolve!();
+
+        #[cfg(feature = "bench")]
+        pub(crate) fn push_backends(backends: &mut Vec<crate::backend::Backend>) {
+            // SAFETY: SIMD128 is enabled at compile time.
+            backends.push(unsafe { crate::backend::backend!("simd128", wasm_simd128) });
+        }
     } else {
         compile_error!("unknown architecture");
     }
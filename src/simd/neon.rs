@@ -5,11 +5,27 @@ use core::arch::arm::*;
 
 use crate::definition::{final_reduction, M61, MODULUS};
 
+// SAFETY: Every bit pattern is a valid vector.
+unsafe impl super::Vector for uint64x2_t {}
+
+/// Calculates the reduction of the number whose least significant digits
+/// are stored in the `len` vectors starting at `ptr`, followed by the
+/// most significant digits stored in `hi`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reading `len` consecutive values of type `uint64x2_t`,
+/// without any alignment requirements. NEON must be supported by the CPU.
 #[target_feature(enable = "neon")]
 unsafe fn reduction_core(ptr: *const uint64x2_t, mut len: usize, mut hi: uint64x2_t) -> M61 {
     let mlo = vdupq_n_u64(MODULUS);
     let mhi = vdupq_n_u64(MODULUS >> 6);
 
+    // Unlike the x86 versions, `hi` isn't pre-reduced here. The mask `mhi`
+    // discards the bits shifted out, so `hr` stays below `2^61 + 2^9` for
+    // arbitrary lanes, and the sum with `lr < 2^61 + 8` can't overflow.
+    // The scalar step below also accepts arbitrary lanes.
+
     while len > 0 {
         len -= 1;
 
@@ -31,90 +47,39 @@ unsafe fn reduction_core(ptr: *const uint64x2_t, mut len: usize, mut hi: uint64x
 
 #[target_feature(enable = "neon")]
 pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
-    let hi = if s.len() & 15 != 0 {
-        let mut lo = 0u64;
-        let mut hi = 0u64;
-
-        let l = s.len() & !15;
-        let mut ptr = s.as_ptr().add(l);
-
-        if s.len() & 8 != 0 {
-            lo = (ptr as *const u64).read_unaligned();
-            ptr = ptr.add(8);
-        }
-
-        let mut tmp = 0;
-        for i in (0..(s.len() & 7)).rev() {
-            tmp <<= 8;
-            tmp |= *ptr.add(i) as u64;
-        }
-
-        if s.len() & 8 != 0 {
-            hi = tmp;
-        } else {
-            lo = tmp;
-        }
-
-        vsetq_lane_u64::<0>(lo, vdupq_n_u64(hi))
-    } else {
-        vdupq_n_u64(0)
-    };
-
-    reduction_core(s.as_ptr() as *const uint64x2_t, s.len() >> 4, hi)
+    let (ptr, len, hi) = super::split_vectors::<u8, uint64x2_t, 16>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[target_feature(enable = "neon")]
 pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
-    let hi = if s.len() & 7 != 0 {
-        let mut arr = [0; 8];
-        let l = s.len() & !7;
-
-        for i in l..s.len() {
-            arr[i - l] = *s.get_unchecked(i);
-        }
-
-        (arr.as_ptr() as *const uint64x2_t).read_unaligned()
-    } else {
-        vdupq_n_u64(0)
-    };
-
-    reduction_core(s.as_ptr() as *const uint64x2_t, s.len() >> 3, hi)
+    let (ptr, len, hi) = super::split_vectors::<u16, uint64x2_t, 8>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[target_feature(enable = "neon")]
 pub unsafe fn reduce_u32(s: &[u32]) -> M61 {
-    let hi = if s.len() & 3 != 0 {
-        let mut arr = [0; 4];
-        let l = s.len() & !3;
-
-        for i in l..s.len() {
-            arr[i - l] = *s.get_unchecked(i);
-        }
-
-        (arr.as_ptr() as *const uint64x2_t).read_unaligned()
-    } else {
-        vdupq_n_u64(0)
-    };
-
-    reduction_core(s.as_ptr() as *const uint64x2_t, s.len() >> 2, hi)
+    let (ptr, len, hi) = super::split_vectors::<u32, uint64x2_t, 4>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[target_feature(enable = "neon")]
 pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
-    let hi = if s.len() & 1 != 0 {
-        let x = s[s.len() - 1];
-        vsetq_lane_u64::<0>((x & MODULUS) + (x >> 61), vdupq_n_u64(0))
-    } else {
-        vdupq_n_u64(0)
-    };
-
-    reduction_core(s.as_ptr() as *const uint64x2_t, s.len() >> 1, hi)
+    let (ptr, len, hi) = super::split_vectors::<u64, uint64x2_t, 2>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    crate::reference::reference_tests! {
+        reduce_u8_reference(u8) => |s| unsafe { reduce_u8(s) };
+        reduce_u16_reference(u16) => |s| unsafe { reduce_u16(s) };
+        reduce_u32_reference(u32) => |s| unsafe { reduce_u32(s) };
+        reduce_u64_reference(u64) => |s| unsafe { reduce_u64(s) };
+    }
+
     #[test]
     fn reduce_u8_max() {
         for len in 0..1000 {
@@ -171,6 +136,25 @@ mod tests {
         }
     }
 
+    /// Exercises tails which fill the low lane of `hi` completely,
+    /// both without and with full vectors preceding them.
+    #[test]
+    fn full_tail_lanes() {
+        for vectors in [0, 1, 2, 7] {
+            let u8s = vec![u8::MAX; vectors * 16 + 15];
+            let u16s = vec![u16::MAX; vectors * 8 + 7];
+            let u32s = vec![u32::MAX; vectors * 4 + 3];
+            let u64s = vec![u64::MAX; vectors * 2 + 1];
+
+            unsafe {
+                assert_eq!(reduce_u8(&u8s), crate::fallback::reduce_u8(&u8s));
+                assert_eq!(reduce_u16(&u16s), crate::fallback::reduce_u16(&u16s));
+                assert_eq!(reduce_u32(&u32s), crate::fallback::reduce_u32(&u32s));
+                assert_eq!(reduce_u64(&u64s), crate::fallback::reduce_u64(&u64s));
+            }
+        }
+    }
+
     quickcheck::quickcheck! {
         fn reduce_u8_correct(slice: Vec<u8>) -> bool {
             let expected = crate::fallback::reduce_u8(&slice);
@@ -5,6 +5,17 @@ use core::arch::x86_64::*;
 
 use crate::definition::{final_reduction, M61, MODULUS};
 
+// SAFETY: Every bit pattern is a valid vector.
+unsafe impl super::Vector for __m128i {}
+
+/// Calculates the reduction of the number whose least significant digits
+/// are stored in the `len` vectors starting at `ptr`, followed by the
+/// most significant digits stored in `hi`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reading `len` consecutive values of type `__m128i`,
+/// without any alignment requirements. SSE2 must be supported by the CPU.
 #[target_feature(enable = "sse2")]
 unsafe fn reduction_core(ptr: *const __m128i, mut len: usize, mut hi: __m128i) -> M61 {
     let mlo = _mm_set1_epi64x(MODULUS as i64);
@@ -36,90 +47,63 @@ unsafe fn reduction_core(ptr: *const __m128i, mut len: usize, mut hi: __m128i) -
 
 #[target_feature(enable = "sse2")]
 pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
-    let hi = if s.len() & 15 != 0 {
-        let mut lo = 0u64;
-        let mut hi = 0u64;
-
-        let l = s.len() & !15;
-        let mut ptr = s.as_ptr().add(l);
-
-        if s.len() & 8 != 0 {
-            lo = (ptr as *const u64).read_unaligned();
-            ptr = ptr.add(8);
-        }
-
-        let mut tmp = 0;
-        for i in (0..(s.len() & 7)).rev() {
-            tmp <<= 8;
-            tmp |= *ptr.add(i) as u64;
-        }
-
-        if s.len() & 8 != 0 {
-            hi = tmp;
-        } else {
-            lo = tmp;
-        }
-
-        _mm_set_epi64x(hi as i64, lo as i64)
-    } else {
-        _mm_setzero_si128()
-    };
-
-    reduction_core(s.as_ptr() as *const __m128i, s.len() >> 4, hi)
+    let (ptr, len, hi) = super::split_vectors::<u8, __m128i, 16>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[target_feature(enable = "sse2")]
 pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
-    let hi = if s.len() & 7 != 0 {
-        let mut arr = [0; 8];
-        let l = s.len() & !7;
-
-        for i in l..s.len() {
-            arr[i - l] = *s.get_unchecked(i);
-        }
-
-        (arr.as_ptr() as *const __m128i).read_unaligned()
-    } else {
-        _mm_setzero_si128()
-    };
-
-    reduction_core(s.as_ptr() as *const __m128i, s.len() >> 3, hi)
+    let (ptr, len, hi) = super::split_vectors::<u16, __m128i, 8>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[target_feature(enable = "sse2")]
 pub unsafe fn reduce_u32(s: &[u32]) -> M61 {
-    let hi = if s.len() & 3 != 0 {
-        let mut arr = [0; 4];
-        let l = s.len() & !3;
-
-        for i in l..s.len() {
-            arr[i - l] = *s.get_unchecked(i);
-        }
-
-        (arr.as_ptr() as *const __m128i).read_unaligned()
-    } else {
-        _mm_setzero_si128()
-    };
-
-    reduction_core(s.as_ptr() as *const __m128i, s.len() >> 2, hi)
+    let (ptr, len, hi) = super::split_vectors::<u32, __m128i, 4>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[target_feature(enable = "sse2")]
 pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
-    let hi = if s.len() & 1 != 0 {
-        let x = s[s.len() - 1];
-        _mm_set_epi64x(0, x as i64)
-    } else {
-        _mm_setzero_si128()
-    };
-
-    reduction_core(s.as_ptr() as *const __m128i, s.len() >> 1, hi)
+    let (ptr, len, hi) = super::split_vectors::<u64, __m128i, 2>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    crate::reference::reference_tests! {
+        reduce_u8_reference(u8) => |s| unsafe { reduce_u8(s) };
+        reduce_u16_reference(u16) => |s| unsafe { reduce_u16(s) };
+        reduce_u32_reference(u32) => |s| unsafe { reduce_u32(s) };
+        reduce_u64_reference(u64) => |s| unsafe { reduce_u64(s) };
+    }
+
+    #[test]
+    fn aligned_and_unaligned_agree() {
+        let bytes: Vec<u8> = (0..2000u32)
+            .map(|x| x.wrapping_mul(0x9e37_79b9).to_be_bytes()[0])
+            .collect();
+        let words: Vec<u64> = (0..300u64)
+            .map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect();
+        let bytes_start = bytes.as_ptr().align_offset(16);
+        let words_start = words.as_ptr().align_offset(16);
+
+        // Only the first offset is aligned to the vector size.
+        for len in [0, 1, 16 - 1, 16, 16 + 1, 250] {
+            for offset in 0..16 {
+                let s = &bytes[bytes_start + offset..][..len];
+                assert_eq!(unsafe { reduce_u8(s) }, crate::fallback::reduce_u8(s));
+            }
+            for offset in 0..16 / 8 {
+                let s = &words[words_start + offset..][..len];
+                assert_eq!(unsafe { reduce_u64(s) }, crate::fallback::reduce_u64(s));
+            }
+        }
+    }
+
     #[test]
     fn reduce_u8_max() {
         for len in 0..1000 {
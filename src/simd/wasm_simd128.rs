@@ -5,11 +5,23 @@ use core::arch::wasm64::*;
 
 use crate::definition::{final_reduction, M61, MODULUS};
 
+// SAFETY: Every bit pattern is a valid vector.
+unsafe impl super::Vector for v128 {}
+
+/// Calculates the reduction of the number whose least significant digits
+/// are stored in the `len` vectors starting at `ptr`, followed by the
+/// most significant digits stored in `hi`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reading `len` consecutive values of type `v128`,
+/// without any alignment requirements. SIMD128 must be supported by the CPU.
 unsafe fn reduction_core(ptr: *const v128, mut len: usize, mut hi: v128) -> M61 {
-    let mlo = u64x2_splat(MODULUS as i64);
-    let mhi = u64x2_splat((MODULUS >> 6) as i64);
+    let mlo = u64x2_splat(MODULUS);
+    let mhi = u64x2_splat(MODULUS >> 6);
 
-    hi = _mm_add_epi64(_mm_and_si128(hi, mlo), _mm_srli_epi64::<61>(hi));
+    // Initial reduction of high elements.
+    hi = u64x2_add(v128_and(hi, mlo), u64x2_shr(hi, 61));
 
     while len > 0 {
         len -= 1;
@@ -31,87 +43,36 @@ unsafe fn reduction_core(ptr: *const v128, mut len: usize, mut hi: v128) -> M61
 }
 
 pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
-    let hi = if s.len() & 15 != 0 {
-        let mut lo = 0u64;
-        let mut hi = 0u64;
-
-        let l = s.len() & !15;
-        let mut ptr = s.as_ptr().add(l);
-
-        if s.len() & 8 != 0 {
-            lo = (ptr as *const u64).read_unaligned();
-            ptr = ptr.add(8);
-        }
-
-        let mut tmp = 0;
-        for i in (0..(s.len() & 7)).rev() {
-            tmp <<= 8;
-            tmp |= *ptr.add(i) as u64;
-        }
-
-        if s.len() & 8 != 0 {
-            hi = tmp;
-        } else {
-            lo = tmp;
-        }
-
-        u64x2(lo, hi)
-    } else {
-        u64x2(0, 0)
-    };
-
-    reduction_core(s.as_ptr() as *const v128, s.len() >> 4, hi)
+    let (ptr, len, hi) = super::split_vectors::<u8, v128, 16>(s);
+    reduction_core(ptr, len, hi)
 }
 
 pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
-    let hi = if s.len() & 7 != 0 {
-        let mut arr = [0; 8];
-        let l = s.len() & !7;
-
-        for i in l..s.len() {
-            arr[i - l] = *s.get_unchecked(i);
-        }
-
-        (arr.as_ptr() as *const v128).read_unaligned()
-    } else {
-        u64x2(0, 0)
-    };
-
-    reduction_core(s.as_ptr() as *const v128, s.len() >> 3, hi)
+    let (ptr, len, hi) = super::split_vectors::<u16, v128, 8>(s);
+    reduction_core(ptr, len, hi)
 }
 
 pub unsafe fn reduce_u32(s: &[u32]) -> M61 {
-    let hi = if s.len() & 3 != 0 {
-        let mut arr = [0; 4];
-        let l = s.len() & !3;
-
-        for i in l..s.len() {
-            arr[i - l] = *s.get_unchecked(i);
-        }
-
-        (arr.as_ptr() as *const v128).read_unaligned()
-    } else {
-        u64x2(0, 0)
-    };
-
-    reduction_core(s.as_ptr() as *const v128, s.len() >> 2, hi)
+    let (ptr, len, hi) = super::split_vectors::<u32, v128, 4>(s);
+    reduction_core(ptr, len, hi)
 }
 
 pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
-    let hi = if s.len() & 1 != 0 {
-        let x = s[s.len() - 1];
-        u64x2((x & MODULUS) + (x >> 61), 0)
-    } else {
-        u64x2(0, 0)
-    };
-
-    reduction_core(s.as_ptr() as *const v128, s.len() >> 1, hi)
+    let (ptr, len, hi) = super::split_vectors::<u64, v128, 2>(s);
+    reduction_core(ptr, len, hi)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    crate::reference::reference_tests! {
+        reduce_u8_reference(u8) => |s| unsafe { reduce_u8(s) };
+        reduce_u16_reference(u16) => |s| unsafe { reduce_u16(s) };
+        reduce_u32_reference(u32) => |s| unsafe { reduce_u32(s) };
+        reduce_u64_reference(u64) => |s| unsafe { reduce_u64(s) };
+    }
+
     #[test]
     fn reduce_u8_max() {
         for len in 0..1000 {
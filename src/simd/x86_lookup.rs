@@ -13,6 +13,18 @@
 //!
 //! This means that any subsequent calls immediately use
 //! the appropriate version.
+//!
+//! ## Thread safety
+//!
+//! All accesses to the static variables are atomic, meaning that
+//! a thread always observes either the initializer or one of the selected
+//! implementations, never a torn value. Relaxed ordering suffices, since
+//! the pointers are the only data being published: they point to code
+//! which exists for the whole lifetime of the program, and no other
+//! memory has to be made visible along with them. Multiple threads may
+//! run the initializer concurrently, which is harmless, as every thread
+//! detects the same features and thus stores the same pointers.
+//! The same reasoning applies to the feature cache of no-std builds.
 
 /// Obtain information about the available target features by
 /// using the `is_x86_feature_detected` macro provided by
@@ -24,11 +36,16 @@ mod detection {
         std::arch::is_x86_feature_detected!("avx2")
     }
 
-    #[cfg(feature = "nightly")]
     #[inline(always)]
     pub(crate) fn has_avx512f() -> bool {
         std::arch::is_x86_feature_detected!("avx512f")
     }
+
+    #[cfg(avx512_intrinsics)]
+    #[inline(always)]
+    pub(crate) fn has_avx512vbmi2() -> bool {
+        std::arch::is_x86_feature_detected!("avx512vbmi2")
+    }
 }
 
 /// Obtain information about the available target features by
@@ -43,10 +60,15 @@ mod detection {
 
     use core::sync::atomic::{AtomicU8, Ordering};
 
+    // The cache stores a set of flags. A value of `UNINIT`
+    // indicates that the features haven't been queried yet,
+    // while the `SSE2` flag is set by every query.
+
     const UNINIT: u8 = 0;
     const SSE2: u8 = 1;
     const AVX2: u8 = 2;
-    const AVX512: u8 = 3;
+    const AVX512: u8 = 4;
+    const AVX512VBMI2: u8 = 8;
 
     static CACHE: AtomicU8 = AtomicU8::new(UNINIT);
 
@@ -67,16 +89,22 @@ mod detection {
                     // Query the extended control register.
                     let xcr0 = _xgetbv(0);
                     // Query extended features.
-                    let ebx = cpuid(0x07, 0x0).ebx;
+                    let extended = cpuid(0x07, 0x0);
+                    let (ebx, ecx) = (extended.ebx, extended.ecx);
 
                     // Support for AVX2
                     if xcr0 & 6 == 6 && ebx & (1 << 5) != 0 {
-                        content = AVX2;
+                        content |= AVX2;
                     }
 
                     // Support for AVX512F
-                    if xcr0 & 224 == 224 && ebx & (1 << 16) != 0 {
-                        content = AVX512;
+                    if xcr0 & 230 == 230 && ebx & (1 << 16) != 0 {
+                        content |= AVX512;
+
+                        // Support for AVX512VBMI2
+                        if ecx & (1 << 6) != 0 {
+                            content |= AVX512VBMI2;
+                        }
                     }
                 };
             }
@@ -90,20 +118,25 @@ mod detection {
 
     #[inline(always)]
     pub(crate) fn has_avx2() -> bool {
-        get_features() >= AVX2
+        get_features() & AVX2 != 0
     }
 
-    #[cfg(feature = "nightly")]
     #[inline(always)]
     pub(crate) fn has_avx512f() -> bool {
-        get_features() >= AVX512
+        get_features() & AVX512 != 0
+    }
+
+    #[cfg(avx512_intrinsics)]
+    #[inline(always)]
+    pub(crate) fn has_avx512vbmi2() -> bool {
+        get_features() & AVX512VBMI2 != 0
     }
 }
 
 use detection::*;
 
 use super::avx2;
-#[cfg(feature = "nightly")]
+#[cfg(avx512_intrinsics)]
 use super::avx512;
 #[cfg(not(target_feature = "avx2"))]
 use super::sse2;
@@ -123,27 +156,34 @@ static FUNC64: AtomicPtr<()> = AtomicPtr::new(reduce_u64_init as *mut ());
 /// Writes the appropiate versions of the functions into the
 /// static variables.
 unsafe fn select() {
-    #[cfg(feature = "nightly")]
-    if has_avx512f() {
-        FUNC8.store(avx512::reduce_u8 as *mut (), Ordering::Relaxed);
-        FUNC16.store(avx512::reduce_u16 as *mut (), Ordering::Relaxed);
-        FUNC32.store(avx512::reduce_u32 as *mut (), Ordering::Relaxed);
-        FUNC64.store(avx512::reduce_u64 as *mut (), Ordering::Relaxed);
-    }
-
-    if has_avx2() {
+    if cfg!(avx512_intrinsics) && has_avx512f() {
+        #[cfg(avx512_intrinsics)]
+        {
+            if has_avx512vbmi2() {
+                FUNC8.store(avx512::reduce_u8 as *mut (), Ordering::Relaxed);
+                FUNC16.store(avx512::reduce_u16 as *mut (), Ordering::Relaxed);
+            } else {
+                // The byte and word variants of the masked loads
+                // used by the avx512 version require AVX512-VBMI2.
+                FUNC8.store(avx2::reduce_u8 as *mut (), Ordering::Relaxed);
+                FUNC16.store(avx2::reduce_u16 as *mut (), Ordering::Relaxed);
+            }
+            FUNC32.store(avx512::reduce_u32 as *mut (), Ordering::Relaxed);
+            FUNC64.store(avx512::reduce_u64 as *mut (), Ordering::Relaxed);
+        }
+    } else if has_avx2() {
         FUNC8.store(avx2::reduce_u8 as *mut (), Ordering::Relaxed);
         FUNC16.store(avx2::reduce_u16 as *mut (), Ordering::Relaxed);
         FUNC32.store(avx2::reduce_u32 as *mut (), Ordering::Relaxed);
         FUNC64.store(avx2::reduce_u64 as *mut (), Ordering::Relaxed);
-    }
-
-    #[cfg(not(target_feature = "avx2"))]
-    {
-        FUNC8.store(sse2::reduce_u8 as *mut (), Ordering::Relaxed);
-        FUNC16.store(sse2::reduce_u16 as *mut (), Ordering::Relaxed);
-        FUNC32.store(sse2::reduce_u32 as *mut (), Ordering::Relaxed);
-        FUNC64.store(sse2::reduce_u64 as *mut (), Ordering::Relaxed);
+    } else {
+        #[cfg(not(target_feature = "avx2"))]
+        {
+            FUNC8.store(sse2::reduce_u8 as *mut (), Ordering::Relaxed);
+            FUNC16.store(sse2::reduce_u16 as *mut (), Ordering::Relaxed);
+            FUNC32.store(sse2::reduce_u32 as *mut (), Ordering::Relaxed);
+            FUNC64.store(sse2::reduce_u64 as *mut (), Ordering::Relaxed);
+        }
     }
 }
 
@@ -158,46 +198,135 @@ type T64 = unsafe fn(&[u64]) -> M61;
 
 unsafe fn reduce_u8_init(s: &[u8]) -> M61 {
     select();
-    let func = transmute::<_, T8>(FUNC8.load(Ordering::Relaxed));
+    let func = transmute::<*mut (), T8>(FUNC8.load(Ordering::Relaxed));
     func(s)
 }
 
 unsafe fn reduce_u16_init(s: &[u16]) -> M61 {
     select();
-    let func = transmute::<_, T16>(FUNC16.load(Ordering::Relaxed));
+    let func = transmute::<*mut (), T16>(FUNC16.load(Ordering::Relaxed));
     func(s)
 }
 
 unsafe fn reduce_u32_init(s: &[u32]) -> M61 {
     select();
-    let func = transmute::<_, T32>(FUNC32.load(Ordering::Relaxed));
+    let func = transmute::<*mut (), T32>(FUNC32.load(Ordering::Relaxed));
     func(s)
 }
 
 unsafe fn reduce_u64_init(s: &[u64]) -> M61 {
     select();
-    let func = transmute::<_, T64>(FUNC64.load(Ordering::Relaxed));
+    let func = transmute::<*mut (), T64>(FUNC64.load(Ordering::Relaxed));
     func(s)
 }
 
 // Definition of the exports.
 
+pub fn force_init() {
+    // SAFETY: `select` only stores pointers to functions
+    // whose target features have been detected.
+    unsafe { select() }
+}
+
+/// Helper macro for the generation of functions returning the selected
+/// implementations, performing the selection first if necessary.
+macro_rules! make_resolve {
+    ($($resolve:ident => $static:ident, $init:ident, $type:ident;)*) => {
+        $(
+            #[allow(dead_code)]
+            pub fn $resolve() -> $type {
+                if $static.load(Ordering::Relaxed) == $init as *mut () {
+                    force_init();
+                }
+
+                // SAFETY: The variable only ever contains
+                // function pointers of the correct type.
+                unsafe { transmute::<*mut (), $type>($static.load(Ordering::Relaxed)) }
+            }
+        )*
+    };
+}
+
+make_resolve! {
+    resolve_u8 => FUNC8, reduce_u8_init, T8;
+    resolve_u16 => FUNC16, reduce_u16_init, T16;
+    resolve_u32 => FUNC32, reduce_u32_init, T32;
+    resolve_u64 => FUNC64, reduce_u64_init, T64;
+}
+
 pub unsafe fn reduce_u8(s: &[u8]) -> M61 {
-    let func = transmute::<_, T8>(FUNC8.load(Ordering::Relaxed));
+    let func = transmute::<*mut (), T8>(FUNC8.load(Ordering::Relaxed));
     func(s)
 }
 
 pub unsafe fn reduce_u16(s: &[u16]) -> M61 {
-    let func = transmute::<_, T16>(FUNC16.load(Ordering::Relaxed));
+    let func = transmute::<*mut (), T16>(FUNC16.load(Ordering::Relaxed));
     func(s)
 }
 
 pub unsafe fn reduce_u32(s: &[u32]) -> M61 {
-    let func = transmute::<_, T32>(FUNC32.load(Ordering::Relaxed));
+    let func = transmute::<*mut (), T32>(FUNC32.load(Ordering::Relaxed));
     func(s)
 }
 
 pub unsafe fn reduce_u64(s: &[u64]) -> M61 {
-    let func = transmute::<_, T64>(FUNC64.load(Ordering::Relaxed));
+    let func = transmute::<*mut (), T64>(FUNC64.load(Ordering::Relaxed));
     func(s)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Barrier, Mutex};
+
+    /// Held by the tests inspecting or resetting the static variables,
+    /// which would otherwise interfere with each other when run in parallel.
+    static DISPATCH: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn selects_best_available() {
+        let _guard = DISPATCH.lock().unwrap();
+        let selected = resolve_u64() as *mut ();
+
+        if cfg!(avx512_intrinsics) && has_avx512f() {
+            #[cfg(avx512_intrinsics)]
+            assert_eq!(selected, avx512::reduce_u64 as *mut ());
+        } else if has_avx2() {
+            assert_eq!(selected, avx2::reduce_u64 as *mut ());
+        }
+    }
+
+    #[test]
+    fn concurrent_initialization() {
+        const THREADS: usize = 16;
+
+        let _guard = DISPATCH.lock().unwrap();
+
+        // Reset the dispatch, so that all threads race to initialize it.
+        // Other tests running concurrently are unaffected, as the
+        // initializers behave like the selected implementations.
+        FUNC8.store(reduce_u8_init as *mut (), Ordering::Relaxed);
+        FUNC16.store(reduce_u16_init as *mut (), Ordering::Relaxed);
+        FUNC32.store(reduce_u32_init as *mut (), Ordering::Relaxed);
+        FUNC64.store(reduce_u64_init as *mut (), Ordering::Relaxed);
+
+        let bytes: Vec<u8> = (0..1000).map(|x| (x * 31) as u8).collect();
+        let words: Vec<u64> = (0..1000u64).map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15)).collect();
+        let expected8 = crate::fallback::reduce_u8(&bytes);
+        let expected64 = crate::fallback::reduce_u64(&words);
+
+        let barrier = Barrier::new(THREADS);
+        std::thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    barrier.wait();
+                    // SAFETY: The dispatch only selects supported implementations.
+                    unsafe {
+                        assert_eq!(reduce_u8(&bytes), expected8);
+                        assert_eq!(reduce_u64(&words), expected64);
+                    }
+                });
+            }
+        });
+    }
+}
@@ -0,0 +1,211 @@
+//! Parallelized reductions using a user-provided executor.
+//!
+//! The chunked accumulation is the same as in the `parallelized` module,
+//! but the work is handed to a [`Spawner`] instead of `std::thread`.
+//! This makes it usable on `no-std` targets providing their own
+//! thread pool. Since the chunk results are combined using an atomic
+//! variable, no allocations are required.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::definition::{shift_exponent, M61, POW2_MOD};
+use crate::M61Reduction;
+
+#[cfg(not(test))]
+const THRESHOLD: usize = 1 << 14;
+
+#[cfg(test)]
+const THRESHOLD: usize = 32;
+
+/// An executor capable of running tasks in parallel.
+pub trait Spawner {
+    /// Returns the number of tasks which can be run in parallel.
+    fn parallelism(&self) -> usize;
+
+    /// Calls `task(i)` for every `i` in `0..count`, possibly in parallel,
+    /// and returns after all calls have finished.
+    fn run(&self, count: usize, task: &(dyn Fn(usize) + Sync));
+}
+
+/// Calculates `s mod (2^61 - 1)`, assuming `s` is a number
+/// base `2^T::BITS`, with digits stored in little-endian ordering.
+///
+/// The input is split into [`Spawner::parallelism`] chunks,
+/// which are then reduced by the tasks run by `spawner`.
+///
+/// ```
+/// use m61_modulus::*;
+///
+/// /// Runs every task on the current thread.
+/// struct Sequential;
+///
+/// impl Spawner for Sequential {
+///     fn parallelism(&self) -> usize {
+///         4
+///     }
+///
+///     fn run(&self, count: usize, task: &(dyn Fn(usize) + Sync)) {
+///         (0..count).for_each(task);
+///     }
+/// }
+///
+/// let v = vec![7u32; 100_000];
+/// assert_eq!(reduce_m61_with_spawner(&v, &Sequential), v.reduce_m61());
+/// ```
+#[must_use]
+pub fn reduce_m61_with_spawner<T, S>(s: &[T], spawner: &S) -> M61
+where
+    T: digit::SpawnDigit,
+    S: Spawner + ?Sized,
+{
+    T::reduce_with_spawner(s, spawner)
+}
+
+/// Splits `s` into chunks which are reduced by the tasks run by `spawner`.
+///
+/// This requires every chunk to be reducible on its own. This doesn't hold
+/// for `[i128]`, where the last limb determines the sign of the whole number.
+fn reduce_chunks<T, S>(s: &[T], spawner: &S) -> M61
+where
+    T: Sync,
+    [T]: M61Reduction,
+    S: Spawner + ?Sized,
+{
+    let parallelism = spawner.parallelism();
+    if s.len() < THRESHOLD || parallelism <= 1 {
+        return s.reduce_m61();
+    }
+
+    let step = (s.len() / parallelism).max(THRESHOLD);
+    let chunk_count = s.len().div_ceil(step);
+
+    // Every chunk shifts the next one by `step * BITS` bits.
+    let chunk_shift = shift_exponent(step, core::mem::size_of::<T>() * 8);
+
+    let result = AtomicU64::new(0);
+
+    spawner.run(chunk_count, &|i| {
+        let part = &s[i * step..s.len().min((i + 1) * step)];
+        let value = part.reduce_m61() * POW2_MOD[shift_exponent(i, chunk_shift)];
+
+        let _ = result.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| {
+            Some((M61(x) + value).get())
+        });
+    });
+
+    M61(result.into_inner())
+}
+
+mod digit {
+    use super::{reduce_chunks, Spawner};
+    use crate::definition::M61;
+    use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping};
+
+    /// A digit type supported by [`reduce_m61_with_spawner`](super::reduce_m61_with_spawner).
+    pub trait SpawnDigit: Sized {
+        /// Reduces `s` using the tasks run by `spawner`.
+        fn reduce_with_spawner<S: Spawner + ?Sized>(s: &[Self], spawner: &S) -> M61;
+    }
+
+    /// Helper macro for implementing [`SpawnDigit`] for the
+    /// types whose digits can be reduced in independent chunks.
+    macro_rules! make_spawn_digit_impl {
+        ($($type:ty),*) => {
+            $(
+                impl SpawnDigit for $type {
+                    #[inline(always)]
+                    fn reduce_with_spawner<S: Spawner + ?Sized>(s: &[Self], spawner: &S) -> M61 {
+                        reduce_chunks(s, spawner)
+                    }
+                }
+            )*
+        };
+    }
+
+    make_spawn_digit_impl!(u8, u16, u32, u64, usize, u128, i8, i16, i32, i64);
+    make_spawn_digit_impl!(Wrapping<u8>, Wrapping<u16>, Wrapping<u32>, Wrapping<u64>);
+    make_spawn_digit_impl!(Wrapping<usize>, NonZeroUsize);
+    make_spawn_digit_impl!(NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64);
+
+    impl<const N: usize> SpawnDigit for [u64; N] {
+        #[inline(always)]
+        fn reduce_with_spawner<S: Spawner + ?Sized>(s: &[Self], spawner: &S) -> M61 {
+            reduce_chunks(s, spawner)
+        }
+    }
+
+    /// The unsigned interpretation of the limbs is reduced in chunks,
+    /// with the sign of the whole number being applied afterwards.
+    impl SpawnDigit for i128 {
+        #[inline]
+        fn reduce_with_spawner<S: Spawner + ?Sized>(s: &[Self], spawner: &S) -> M61 {
+            crate::reduce_i128_with(s, |s| reduce_chunks(s, spawner))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs every task on its own thread.
+    struct Threads(usize);
+
+    impl Spawner for Threads {
+        fn parallelism(&self) -> usize {
+            self.0
+        }
+
+        fn run(&self, count: usize, task: &(dyn Fn(usize) + Sync)) {
+            std::thread::scope(|scope| {
+                for i in 0..count {
+                    scope.spawn(move || task(i));
+                }
+            });
+        }
+    }
+
+    #[cfg_attr(miri, ignore = "the implementation is done using safe Rust")]
+    #[test]
+    fn reduce_with_spawner_correct() {
+        for i in (0..1000).step_by(3) {
+            let v: Vec<u16> = (0..i).map(|x| (x * 4099) as u16).collect();
+            let w: Vec<usize> = (0..i)
+                .map(|x: usize| x.wrapping_mul(usize::MAX / 3))
+                .collect();
+
+            for threads in [0, 1, 2, 3, 7, 16] {
+                assert_eq!(
+                    reduce_m61_with_spawner(&v, &Threads(threads)),
+                    v.reduce_m61()
+                );
+                assert_eq!(
+                    reduce_m61_with_spawner(&w, &Threads(threads)),
+                    w.reduce_m61()
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(miri, ignore = "the implementation is done using safe Rust")]
+    #[test]
+    fn reduce_i128_with_spawner_correct() {
+        for i in (0..1000).step_by(7) {
+            let v: Vec<i128> = (0..i)
+                .map(|x: i128| x.wrapping_mul(0x1e37_79b9_7f4a_7c15_f39c_c060_5ced_c835))
+                .collect();
+            let negative: Vec<i128> = v.iter().map(|&x| x | i128::MIN).collect();
+
+            for threads in [0, 1, 2, 3, 7, 16] {
+                assert_eq!(
+                    reduce_m61_with_spawner(&v, &Threads(threads)),
+                    v.reduce_m61()
+                );
+                assert_eq!(
+                    reduce_m61_with_spawner(&negative, &Threads(threads)),
+                    negative.reduce_m61()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Differential tests comparing every backend available on the
+//! current CPU against each other using the same inputs.
+//!
+//! Each backend is also tested against the scalar implementation in
+//! isolation, but running all of them here ensures that a divergence
+//! between two vectorized implementations is caught on hosts
+//! supporting both, e.g. AVX2 and AVX512.
+//!
+//! Run using `cargo test --features bench`.
+
+#![cfg(feature = "bench")]
+
+use m61_modulus::backend::{available, Backend};
+use m61_modulus::M61;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Lengths in bytes, covering partially filled vectors as
+/// well as inputs spanning many cache lines.
+const LENGTHS: [usize; 8] = [0, 1, 63, 64, 65, 4095, 1 << 16, (1 << 20) + 7];
+
+/// Asserts that all backends agree on the reduction of `s`.
+fn check<T>(s: &[T], reduce: impl Fn(&Backend, &[T]) -> M61) {
+    let backends = available();
+    let expected = reduce(&backends[0], s);
+
+    for backend in &backends[1..] {
+        assert_eq!(
+            reduce(backend, s),
+            expected,
+            "backend {:?} diverges from {:?} for {} elements",
+            backend,
+            backends[0],
+            s.len(),
+        );
+    }
+}
+
+#[test]
+fn backends_agree_on_random_input() {
+    let mut rng = StdRng::seed_from_u64(0x6d36_315f_6469_6666);
+
+    for len in LENGTHS {
+        let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        let words: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        let dwords: Vec<u32> = (0..len / 4).map(|_| rng.gen()).collect();
+        let qwords: Vec<u64> = (0..len / 8).map(|_| rng.gen()).collect();
+
+        check(&bytes, Backend::reduce_u8);
+        check(&words, Backend::reduce_u16);
+        check(&dwords, Backend::reduce_u32);
+        check(&qwords, Backend::reduce_u64);
+    }
+}
+
+#[test]
+fn backends_agree_on_saturated_input() {
+    // Saturated digits maximize the intermediate values of the accumulators.
+    for len in LENGTHS {
+        check(&vec![u8::MAX; len], Backend::reduce_u8);
+        check(&vec![u16::MAX; len / 2], Backend::reduce_u16);
+        check(&vec![u32::MAX; len / 4], Backend::reduce_u32);
+        check(&vec![u64::MAX; len / 8], Backend::reduce_u64);
+    }
+}
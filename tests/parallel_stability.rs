@@ -0,0 +1,74 @@
+//! Stability tests for the parallelized reductions.
+//!
+//! The same buffer is reduced repeatedly with varying thread counts,
+//! asserting that the result never changes. The thread counts aren't
+//! limited to the available parallelism, so the threads are spawned
+//! and joined even on hosts with a single core.
+//!
+//! Besides `cargo test --features bench`, these tests are meant to be
+//! run under the thread sanitizer to catch data races:
+//! ```text
+//! RUSTFLAGS=-Zsanitizer=thread cargo +nightly test -Zbuild-std \
+//!     --target x86_64-unknown-linux-gnu --features bench --test parallel_stability
+//! ```
+
+#![cfg(feature = "bench")]
+
+use m61_modulus::backend::{reduce_u64_threads, reduce_u8_threads};
+use m61_modulus::{M61Reduction, ParallelStrategy};
+
+/// Large enough to give every one of the threads several chunks.
+const LEN: usize = 1 << 19;
+
+/// The number of times every configuration is repeated.
+const REPEATS: usize = 4;
+
+const STRATEGIES: [ParallelStrategy; 3] = [
+    ParallelStrategy::Static,
+    ParallelStrategy::OverDecomposed { factor: 1 },
+    ParallelStrategy::OverDecomposed { factor: 4 },
+];
+
+fn input() -> Vec<u64> {
+    (0..LEN as u64)
+        .map(|x| x.wrapping_mul(0x9e37_79b9_7f4a_7c15).rotate_left(17))
+        .collect()
+}
+
+#[cfg_attr(miri, ignore = "spawns many threads")]
+#[test]
+fn stable_across_thread_counts() {
+    let v = input();
+    let bytes: Vec<u8> = v.iter().flat_map(|x| x.to_le_bytes()).collect();
+    let expected = v.reduce_m61();
+
+    for threads in [1, 2, 3, 4, 7, 8, 16] {
+        for strategy in STRATEGIES {
+            for _ in 0..REPEATS {
+                assert_eq!(
+                    reduce_u64_threads(&v, threads, strategy),
+                    expected,
+                    "{threads} threads, {strategy:?}"
+                );
+                assert_eq!(
+                    reduce_u8_threads(&bytes, threads, strategy),
+                    expected,
+                    "{threads} threads, {strategy:?}"
+                );
+            }
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore = "spawns many threads")]
+#[test]
+fn stable_through_public_api() {
+    let v = input();
+    let expected = v.reduce_m61();
+
+    for threads in [0, 1, 2, 4, 64, usize::MAX] {
+        for _ in 0..REPEATS {
+            assert_eq!(v.reduce_m61_parallelized(threads), expected);
+        }
+    }
+}